@@ -1,4 +1,15 @@
-use crate::PDU_NOT_AVAILABLE;
+use core::time::Duration;
+
+use crate::{
+    protocol::{self, AcknowledgementControl},
+    slots,
+    spn::EngineTorqueMode,
+    transport::{
+        ConnectionReceiver, ConnectionReceiverState, ConnectionTransmitter,
+        ConnectionTransmitterState, Timer, TransportError,
+    },
+    Frame, FrameBuilder, IdBuilder, PDU_ERROR, PDU_MAX_LENGTH, PDU_NOT_AVAILABLE, PGN,
+};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum LampStatus {
@@ -64,44 +75,67 @@ pub struct Message1 {
     pub red_stop_lamp_flash: Option<FlashStatus>,
     pub malfunction_indicator_lamp_flash: Option<FlashStatus>,
     pub suspect_parameter_number: u32,
-    pub failure_mode_identifier: u8,
+    pub failure_mode_identifier: Fmi,
     pub spn_conversion_method: u8,
     pub occurrence_count: u8,
 }
 
 impl Message1 {
     pub fn from_pdu(pdu: &[u8]) -> Self {
+        let lamps = Lamps::from_pdu(pdu);
+        let dtc = Dtc::from_pdu(&pdu[2..]);
+
         Self {
-            protect_lamp: LampStatus::from_value(pdu[0]),
-            amber_warning_lamp: LampStatus::from_value(pdu[0] >> 2),
-            red_stop_lamp: LampStatus::from_value(pdu[0] >> 4),
-            malfunction_indicator_lamp: LampStatus::from_value(pdu[0] >> 6),
-            protect_lamp_flash: FlashStatus::from_value(pdu[1]),
-            amber_warning_lamp_flash: FlashStatus::from_value(pdu[1] >> 2),
-            red_stop_lamp_flash: FlashStatus::from_value(pdu[1] >> 4),
-            malfunction_indicator_lamp_flash: FlashStatus::from_value(pdu[1] >> 6),
-            suspect_parameter_number: u32::from_le_bytes([pdu[2], pdu[3], pdu[4] >> 6, 0]),
-            failure_mode_identifier: pdu[4] & 0x1F,
-            spn_conversion_method: pdu[5] >> 7,
-            occurrence_count: pdu[5] & 0x7F,
+            protect_lamp: lamps.protect_lamp,
+            amber_warning_lamp: lamps.amber_warning_lamp,
+            red_stop_lamp: lamps.red_stop_lamp,
+            malfunction_indicator_lamp: lamps.malfunction_indicator_lamp,
+            protect_lamp_flash: lamps.protect_lamp_flash,
+            amber_warning_lamp_flash: lamps.amber_warning_lamp_flash,
+            red_stop_lamp_flash: lamps.red_stop_lamp_flash,
+            malfunction_indicator_lamp_flash: lamps.malfunction_indicator_lamp_flash,
+            suspect_parameter_number: dtc.suspect_parameter_number,
+            failure_mode_identifier: dtc.failure_mode_identifier,
+            spn_conversion_method: dtc.spn_conversion_method,
+            occurrence_count: dtc.occurrence_count,
+        }
+    }
+
+    /// Returns the lamp status.
+    pub fn lamps(&self) -> Lamps {
+        Lamps {
+            protect_lamp: self.protect_lamp,
+            amber_warning_lamp: self.amber_warning_lamp,
+            red_stop_lamp: self.red_stop_lamp,
+            malfunction_indicator_lamp: self.malfunction_indicator_lamp,
+            protect_lamp_flash: self.protect_lamp_flash,
+            amber_warning_lamp_flash: self.amber_warning_lamp_flash,
+            red_stop_lamp_flash: self.red_stop_lamp_flash,
+            malfunction_indicator_lamp_flash: self.malfunction_indicator_lamp_flash,
+        }
+    }
+
+    /// Returns the diagnostic trouble code.
+    pub fn dtc(&self) -> Dtc {
+        Dtc {
+            suspect_parameter_number: self.suspect_parameter_number,
+            failure_mode_identifier: self.failure_mode_identifier,
+            spn_conversion_method: self.spn_conversion_method,
+            occurrence_count: self.occurrence_count,
         }
     }
 
     pub fn to_pdu(&self) -> [u8; 8] {
+        let lamps = self.lamps().to_pdu();
+        let dtc = self.dtc().to_pdu();
+
         [
-            LampStatus::to_value(self.protect_lamp)
-                | LampStatus::to_value(self.amber_warning_lamp) << 2
-                | LampStatus::to_value(self.red_stop_lamp) << 4
-                | LampStatus::to_value(self.malfunction_indicator_lamp) << 6,
-            FlashStatus::to_value(self.protect_lamp_flash)
-                | FlashStatus::to_value(self.amber_warning_lamp_flash) << 2
-                | FlashStatus::to_value(self.red_stop_lamp_flash) << 4
-                | FlashStatus::to_value(self.malfunction_indicator_lamp_flash) << 6,
-            (self.suspect_parameter_number & 0xFF) as u8,
-            ((self.suspect_parameter_number >> 8) & 0xFF) as u8,
-            ((self.suspect_parameter_number >> 16) & 0x3F) as u8
-                | (self.failure_mode_identifier & 0x1F),
-            (self.spn_conversion_method & 0x01) << 7 | (self.occurrence_count & 0x7F),
+            lamps[0],
+            lamps[1],
+            dtc[0],
+            dtc[1],
+            dtc[2],
+            dtc[3],
             PDU_NOT_AVAILABLE,
             PDU_NOT_AVAILABLE,
         ]
@@ -131,167 +165,3051 @@ impl core::fmt::Display for Message1 {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Number of bytes in a diagnostic trouble code.
+pub const DTC_LENGTH: usize = 4;
 
-    #[test]
-    fn diagnostic_1_message_1() {
-        let diagnostic_message = Message1::from_pdu(&[0x57, 0xFF, 0x9F, 0x00, 0x03, 0x01]);
+/// Failure mode identifier (FMI).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Fmi {
+    /// Data valid but above normal operational range - most severe level.
+    AboveNormalMostSevere,
+    /// Data valid but below normal operational range - most severe level.
+    BelowNormalMostSevere,
+    /// Data erratic, intermittent or incorrect.
+    DataErratic,
+    /// Voltage above normal, or shorted to high source.
+    VoltageAboveNormal,
+    /// Voltage below normal, or shorted to low source.
+    VoltageBelowNormal,
+    /// Current below normal or open circuit.
+    CurrentBelowNormal,
+    /// Current above normal or grounded circuit.
+    CurrentAboveNormal,
+    /// Mechanical system not responding or out of adjustment.
+    MechanicalSystemNotResponding,
+    /// Abnormal frequency or pulse width or period.
+    AbnormalFrequency,
+    /// Abnormal update rate.
+    AbnormalUpdateRate,
+    /// Abnormal rate of change.
+    AbnormalRateOfChange,
+    /// Root cause not known.
+    RootCauseNotKnown,
+    /// Bad intelligent device or component.
+    BadDevice,
+    /// Out of calibration.
+    OutOfCalibration,
+    /// Special instructions.
+    SpecialInstructions,
+    /// Data valid but above normal operating range - least severe level.
+    AboveNormalLeastSevere,
+    /// Data valid but above normal operating range - moderately severe level.
+    AboveNormalModeratelySevere,
+    /// Data valid but below normal operating range - least severe level.
+    BelowNormalLeastSevere,
+    /// Data valid but below normal operating range - moderately severe level.
+    BelowNormalModeratelySevere,
+    /// Received network data in error.
+    ReceivedNetworkDataInError,
+    /// Data drifted high.
+    DataDriftedHigh,
+    /// Data drifted low.
+    DataDriftedLow,
+    /// Reserved for SAE assignment.
+    Reserved(u8),
+    /// Condition exists.
+    ConditionExists,
+}
 
-        assert_eq!(diagnostic_message.protect_lamp, None);
-        assert_eq!(diagnostic_message.amber_warning_lamp, Some(LampStatus::On));
-        assert_eq!(diagnostic_message.red_stop_lamp, Some(LampStatus::On));
-        assert_eq!(
-            diagnostic_message.malfunction_indicator_lamp,
-            Some(LampStatus::On)
-        );
-        assert_eq!(diagnostic_message.protect_lamp_flash, None);
-        assert_eq!(diagnostic_message.amber_warning_lamp_flash, None);
-        assert_eq!(diagnostic_message.red_stop_lamp_flash, None);
-        assert_eq!(diagnostic_message.malfunction_indicator_lamp_flash, None);
-        assert_eq!(diagnostic_message.suspect_parameter_number, 159);
-        assert_eq!(diagnostic_message.failure_mode_identifier, 3);
-        assert_eq!(diagnostic_message.spn_conversion_method, 0);
-        assert_eq!(diagnostic_message.occurrence_count, 1);
+/// Failure mode severity level.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FmiSeverity {
+    LeastSevere,
+    ModeratelySevere,
+    MostSevere,
+}
+
+/// Failure mode category.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FmiCategory {
+    /// Data valid but outside the normal range.
+    Range,
+    /// Electrical fault in the circuit.
+    Electrical,
+    /// Abnormal signal or data.
+    Signal,
+    /// Fault in the network data.
+    Network,
+    /// Fault in a mechanical system, device or calibration.
+    Component,
+    /// Any other failure mode.
+    Other,
+}
+
+impl Fmi {
+    /// Returns the severity level of the failure mode.
+    ///
+    /// Only failure modes for data outside the normal range have a severity level.
+    pub fn severity(&self) -> Option<FmiSeverity> {
+        match self {
+            Self::AboveNormalMostSevere | Self::BelowNormalMostSevere => {
+                Some(FmiSeverity::MostSevere)
+            }
+            Self::AboveNormalModeratelySevere | Self::BelowNormalModeratelySevere => {
+                Some(FmiSeverity::ModeratelySevere)
+            }
+            Self::AboveNormalLeastSevere | Self::BelowNormalLeastSevere => {
+                Some(FmiSeverity::LeastSevere)
+            }
+            _ => None,
+        }
     }
 
-    #[test]
-    fn diagnostic_1_message_2() {
-        let diagnostic_message = Message1::from_pdu(&[0x57, 0xFF, 0xFB, 0x06, 0x0B, 0x32]);
+    /// Returns the category of the failure mode.
+    pub fn category(&self) -> FmiCategory {
+        match self {
+            Self::AboveNormalMostSevere
+            | Self::BelowNormalMostSevere
+            | Self::AboveNormalLeastSevere
+            | Self::AboveNormalModeratelySevere
+            | Self::BelowNormalLeastSevere
+            | Self::BelowNormalModeratelySevere
+            | Self::DataDriftedHigh
+            | Self::DataDriftedLow => FmiCategory::Range,
+            Self::VoltageAboveNormal
+            | Self::VoltageBelowNormal
+            | Self::CurrentBelowNormal
+            | Self::CurrentAboveNormal => FmiCategory::Electrical,
+            Self::DataErratic
+            | Self::AbnormalFrequency
+            | Self::AbnormalUpdateRate
+            | Self::AbnormalRateOfChange => FmiCategory::Signal,
+            Self::ReceivedNetworkDataInError => FmiCategory::Network,
+            Self::MechanicalSystemNotResponding | Self::BadDevice | Self::OutOfCalibration => {
+                FmiCategory::Component
+            }
+            Self::RootCauseNotKnown
+            | Self::SpecialInstructions
+            | Self::Reserved(_)
+            | Self::ConditionExists => FmiCategory::Other,
+        }
+    }
+}
 
-        assert_eq!(diagnostic_message.protect_lamp, None);
-        assert_eq!(diagnostic_message.amber_warning_lamp, Some(LampStatus::On));
-        assert_eq!(diagnostic_message.red_stop_lamp, Some(LampStatus::On));
-        assert_eq!(
-            diagnostic_message.malfunction_indicator_lamp,
-            Some(LampStatus::On)
-        );
-        assert_eq!(diagnostic_message.protect_lamp_flash, None);
-        assert_eq!(diagnostic_message.amber_warning_lamp_flash, None);
-        assert_eq!(diagnostic_message.red_stop_lamp_flash, None);
-        assert_eq!(diagnostic_message.malfunction_indicator_lamp_flash, None);
-        assert_eq!(diagnostic_message.suspect_parameter_number, 1787);
-        assert_eq!(diagnostic_message.failure_mode_identifier, 11);
-        assert_eq!(diagnostic_message.spn_conversion_method, 0);
-        assert_eq!(diagnostic_message.occurrence_count, 50);
+impl From<u8> for Fmi {
+    fn from(value: u8) -> Self {
+        match value & 0x1F {
+            0 => Fmi::AboveNormalMostSevere,
+            1 => Fmi::BelowNormalMostSevere,
+            2 => Fmi::DataErratic,
+            3 => Fmi::VoltageAboveNormal,
+            4 => Fmi::VoltageBelowNormal,
+            5 => Fmi::CurrentBelowNormal,
+            6 => Fmi::CurrentAboveNormal,
+            7 => Fmi::MechanicalSystemNotResponding,
+            8 => Fmi::AbnormalFrequency,
+            9 => Fmi::AbnormalUpdateRate,
+            10 => Fmi::AbnormalRateOfChange,
+            11 => Fmi::RootCauseNotKnown,
+            12 => Fmi::BadDevice,
+            13 => Fmi::OutOfCalibration,
+            14 => Fmi::SpecialInstructions,
+            15 => Fmi::AboveNormalLeastSevere,
+            16 => Fmi::AboveNormalModeratelySevere,
+            17 => Fmi::BelowNormalLeastSevere,
+            18 => Fmi::BelowNormalModeratelySevere,
+            19 => Fmi::ReceivedNetworkDataInError,
+            20 => Fmi::DataDriftedHigh,
+            21 => Fmi::DataDriftedLow,
+            31 => Fmi::ConditionExists,
+            value => Fmi::Reserved(value),
+        }
     }
+}
 
-    #[test]
-    fn diagnostic_1_message_3() {
-        let diagnostic_message = Message1::from_pdu(&[0x40, 0xFF, 0x7F, 0x02, 0x02, 0x00]);
+impl From<Fmi> for u8 {
+    fn from(value: Fmi) -> Self {
+        match value {
+            Fmi::AboveNormalMostSevere => 0,
+            Fmi::BelowNormalMostSevere => 1,
+            Fmi::DataErratic => 2,
+            Fmi::VoltageAboveNormal => 3,
+            Fmi::VoltageBelowNormal => 4,
+            Fmi::CurrentBelowNormal => 5,
+            Fmi::CurrentAboveNormal => 6,
+            Fmi::MechanicalSystemNotResponding => 7,
+            Fmi::AbnormalFrequency => 8,
+            Fmi::AbnormalUpdateRate => 9,
+            Fmi::AbnormalRateOfChange => 10,
+            Fmi::RootCauseNotKnown => 11,
+            Fmi::BadDevice => 12,
+            Fmi::OutOfCalibration => 13,
+            Fmi::SpecialInstructions => 14,
+            Fmi::AboveNormalLeastSevere => 15,
+            Fmi::AboveNormalModeratelySevere => 16,
+            Fmi::BelowNormalLeastSevere => 17,
+            Fmi::BelowNormalModeratelySevere => 18,
+            Fmi::ReceivedNetworkDataInError => 19,
+            Fmi::DataDriftedHigh => 20,
+            Fmi::DataDriftedLow => 21,
+            Fmi::Reserved(value) => value & 0x1F,
+            Fmi::ConditionExists => 31,
+        }
+    }
+}
 
-        assert_eq!(diagnostic_message.protect_lamp, Some(LampStatus::Off));
-        assert_eq!(diagnostic_message.amber_warning_lamp, Some(LampStatus::Off));
-        assert_eq!(diagnostic_message.red_stop_lamp, Some(LampStatus::Off));
-        assert_eq!(
-            diagnostic_message.malfunction_indicator_lamp,
-            Some(LampStatus::On)
-        );
-        assert_eq!(diagnostic_message.protect_lamp_flash, None);
-        assert_eq!(diagnostic_message.amber_warning_lamp_flash, None);
-        assert_eq!(diagnostic_message.red_stop_lamp_flash, None);
-        assert_eq!(diagnostic_message.malfunction_indicator_lamp_flash, None);
-        assert_eq!(diagnostic_message.suspect_parameter_number, 639);
-        assert_eq!(diagnostic_message.failure_mode_identifier, 2);
-        assert_eq!(diagnostic_message.spn_conversion_method, 0);
-        assert_eq!(diagnostic_message.occurrence_count, 0);
+impl core::fmt::Display for Fmi {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Fmi::AboveNormalMostSevere => write!(
+                f,
+                "Data Valid But Above Normal Operational Range - Most Severe Level"
+            ),
+            Fmi::BelowNormalMostSevere => write!(
+                f,
+                "Data Valid But Below Normal Operational Range - Most Severe Level"
+            ),
+            Fmi::DataErratic => write!(f, "Data Erratic, Intermittent Or Incorrect"),
+            Fmi::VoltageAboveNormal => write!(f, "Voltage Above Normal, Or Shorted To High Source"),
+            Fmi::VoltageBelowNormal => write!(f, "Voltage Below Normal, Or Shorted To Low Source"),
+            Fmi::CurrentBelowNormal => write!(f, "Current Below Normal Or Open Circuit"),
+            Fmi::CurrentAboveNormal => write!(f, "Current Above Normal Or Grounded Circuit"),
+            Fmi::MechanicalSystemNotResponding => {
+                write!(f, "Mechanical System Not Responding Or Out Of Adjustment")
+            }
+            Fmi::AbnormalFrequency => write!(f, "Abnormal Frequency Or Pulse Width Or Period"),
+            Fmi::AbnormalUpdateRate => write!(f, "Abnormal Update Rate"),
+            Fmi::AbnormalRateOfChange => write!(f, "Abnormal Rate Of Change"),
+            Fmi::RootCauseNotKnown => write!(f, "Root Cause Not Known"),
+            Fmi::BadDevice => write!(f, "Bad Intelligent Device Or Component"),
+            Fmi::OutOfCalibration => write!(f, "Out Of Calibration"),
+            Fmi::SpecialInstructions => write!(f, "Special Instructions"),
+            Fmi::AboveNormalLeastSevere => write!(
+                f,
+                "Data Valid But Above Normal Operating Range - Least Severe Level"
+            ),
+            Fmi::AboveNormalModeratelySevere => write!(
+                f,
+                "Data Valid But Above Normal Operating Range - Moderately Severe Level"
+            ),
+            Fmi::BelowNormalLeastSevere => write!(
+                f,
+                "Data Valid But Below Normal Operating Range - Least Severe Level"
+            ),
+            Fmi::BelowNormalModeratelySevere => write!(
+                f,
+                "Data Valid But Below Normal Operating Range - Moderately Severe Level"
+            ),
+            Fmi::ReceivedNetworkDataInError => write!(f, "Received Network Data In Error"),
+            Fmi::DataDriftedHigh => write!(f, "Data Drifted High"),
+            Fmi::DataDriftedLow => write!(f, "Data Drifted Low"),
+            Fmi::Reserved(_) => write!(f, "Reserved For SAE Assignment"),
+            Fmi::ConditionExists => write!(f, "Condition Exists"),
+        }
     }
+}
 
-    #[test]
-    fn diagnostic_1_message_4() {
-        let diagnostic_message = Message1::from_pdu(&[0x00, 0xFF, 0x00, 0x00, 0x00, 0x00]);
+/// SPN conversion method.
+///
+/// DTCs with the conversion method (CM) bit cleared use version 4. Older ECUs set the CM bit
+/// and use one of the legacy versions, which differ in the bit order of the SPN. Which legacy
+/// version is used can not be derived from the DTC and must be known for the ECU.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SpnConversionMethod {
+    /// SPN bits 18 to 3 most significant byte first, bits 2 to 0 in the FMI byte.
+    Version1,
+    /// SPN bits 15 to 0 most significant byte first, bits 18 to 16 in the FMI byte.
+    Version2,
+    /// SPN bits 15 to 0 least significant byte first, bits 18 to 16 in the FMI byte.
+    Version3,
+    /// Same bit order as version 3, with the CM bit cleared.
+    Version4,
+}
 
-        assert_eq!(diagnostic_message.protect_lamp, Some(LampStatus::Off));
-        assert_eq!(diagnostic_message.amber_warning_lamp, Some(LampStatus::Off));
-        assert_eq!(diagnostic_message.red_stop_lamp, Some(LampStatus::Off));
-        assert_eq!(
-            diagnostic_message.malfunction_indicator_lamp,
-            Some(LampStatus::Off)
-        );
-        assert_eq!(diagnostic_message.protect_lamp_flash, None);
-        assert_eq!(diagnostic_message.amber_warning_lamp_flash, None);
-        assert_eq!(diagnostic_message.red_stop_lamp_flash, None);
-        assert_eq!(diagnostic_message.malfunction_indicator_lamp_flash, None);
-        assert_eq!(diagnostic_message.suspect_parameter_number, 0);
-        assert_eq!(diagnostic_message.failure_mode_identifier, 0);
-        assert_eq!(diagnostic_message.spn_conversion_method, 0);
-        assert_eq!(diagnostic_message.occurrence_count, 0);
+impl SpnConversionMethod {
+    /// Encode the SPN into the first 3 bytes of a DTC.
+    fn encode(self, spn: u32) -> [u8; 3] {
+        let spn = spn & 0x7FFFF;
+
+        match self {
+            Self::Version1 => [
+                (spn >> 11) as u8,
+                (spn >> 3) as u8,
+                ((spn & 0x07) as u8) << 5,
+            ],
+            Self::Version2 => [(spn >> 8) as u8, spn as u8, ((spn >> 16) as u8) << 5],
+            Self::Version3 | Self::Version4 => {
+                [spn as u8, (spn >> 8) as u8, ((spn >> 16) as u8) << 5]
+            }
+        }
     }
 
-    #[test]
-    fn diagnostic_1_message_5() {
-        let diagnostic_message_encoded = Message1 {
-            protect_lamp: Some(LampStatus::Off),
-            amber_warning_lamp: Some(LampStatus::Off),
-            red_stop_lamp: Some(LampStatus::Off),
-            malfunction_indicator_lamp: Some(LampStatus::On),
-            protect_lamp_flash: None,
-            amber_warning_lamp_flash: None,
-            red_stop_lamp_flash: None,
-            malfunction_indicator_lamp_flash: None,
-            suspect_parameter_number: 639,
-            failure_mode_identifier: 2,
-            spn_conversion_method: 0,
-            occurrence_count: 0,
+    /// Decode the SPN from the first 3 bytes of a DTC.
+    fn decode(self, pdu: &[u8]) -> u32 {
+        let high = (pdu[2] >> 5) as u32;
+
+        match self {
+            Self::Version1 => (pdu[0] as u32) << 11 | (pdu[1] as u32) << 3 | high,
+            Self::Version2 => high << 16 | (pdu[0] as u32) << 8 | pdu[1] as u32,
+            Self::Version3 | Self::Version4 => u32::from_le_bytes([pdu[0], pdu[1], pdu[2] >> 5, 0]),
         }
-        .to_pdu();
+    }
+}
 
-        assert_eq!(
-            diagnostic_message_encoded,
-            [0x40, 0xFF, 0x7F, 0x02, 0x02, 0x00, 0xFF, 0xFF]
-        );
+/// Diagnostic trouble code (DTC).
+///
+/// A DTC is made up of the 19-bit suspect parameter number (SPN), the 5-bit failure mode
+/// identifier (FMI), the SPN conversion method (CM) bit and the 7-bit occurrence count (OC).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Dtc {
+    pub suspect_parameter_number: u32,
+    pub failure_mode_identifier: Fmi,
+    pub spn_conversion_method: u8,
+    pub occurrence_count: u8,
+}
+
+impl Dtc {
+    /// Decode a DTC.
+    ///
+    /// The SPN is decoded in version 4 bit order, which is shared with version 3.
+    pub fn from_pdu(pdu: &[u8]) -> Self {
+        Self::from_pdu_with_method(pdu, SpnConversionMethod::Version4)
     }
 
-    #[test]
-    fn diagnostic_1_message_6() {
-        let diagnostic_message_encoded = Message1 {
-            protect_lamp: Some(LampStatus::On),
-            amber_warning_lamp: Some(LampStatus::Off),
-            red_stop_lamp: Some(LampStatus::On),
-            malfunction_indicator_lamp: Some(LampStatus::On),
-            protect_lamp_flash: Some(FlashStatus::Slow),
-            amber_warning_lamp_flash: None,
-            red_stop_lamp_flash: Some(FlashStatus::Slow),
-            malfunction_indicator_lamp_flash: Some(FlashStatus::Fast),
-            suspect_parameter_number: 639,
-            failure_mode_identifier: 2,
-            spn_conversion_method: 0,
-            occurrence_count: 0,
+    /// Decode a DTC with the conversion method used by the ECU when the CM bit is set.
+    pub fn from_pdu_with_method(pdu: &[u8], method: SpnConversionMethod) -> Self {
+        let spn_conversion_method = pdu[3] >> 7;
+        let method = if spn_conversion_method == 0 {
+            SpnConversionMethod::Version4
+        } else {
+            method
+        };
+
+        Self {
+            suspect_parameter_number: method.decode(pdu),
+            failure_mode_identifier: Fmi::from(pdu[2]),
+            spn_conversion_method,
+            occurrence_count: pdu[3] & 0x7F,
         }
-        .to_pdu();
+    }
 
-        let diagnostic_message_decoded = Message1::from_pdu(&diagnostic_message_encoded);
+    /// Encode the DTC.
+    ///
+    /// The SPN is encoded in version 4 bit order, the CM bit is taken from the DTC.
+    pub fn to_pdu(&self) -> [u8; DTC_LENGTH] {
+        let spn = SpnConversionMethod::Version4.encode(self.suspect_parameter_number);
 
-        assert_eq!(
-            diagnostic_message_decoded.protect_lamp,
-            Some(LampStatus::On)
-        );
-        assert_eq!(
-            diagnostic_message_decoded.amber_warning_lamp,
-            Some(LampStatus::Off)
-        );
-        assert_eq!(
-            diagnostic_message_decoded.red_stop_lamp,
-            Some(LampStatus::On)
-        );
-        assert_eq!(
-            diagnostic_message_decoded.malfunction_indicator_lamp,
-            Some(LampStatus::On)
-        );
+        [
+            spn[0],
+            spn[1],
+            spn[2] | u8::from(self.failure_mode_identifier),
+            (self.spn_conversion_method & 0x01) << 7 | (self.occurrence_count & 0x7F),
+        ]
+    }
 
-        assert_eq!(
-            diagnostic_message_decoded.protect_lamp_flash,
-            Some(FlashStatus::Slow)
-        );
-        assert_eq!(diagnostic_message_decoded.amber_warning_lamp_flash, None);
-        assert_eq!(
-            diagnostic_message_decoded.red_stop_lamp_flash,
-            Some(FlashStatus::Slow)
-        );
-        assert_eq!(
-            diagnostic_message_decoded.malfunction_indicator_lamp_flash,
-            Some(FlashStatus::Fast)
-        );
+    /// Encode the DTC with a conversion method.
+    ///
+    /// The CM bit is cleared for version 4 and set for the legacy versions.
+    pub fn to_pdu_with_method(&self, method: SpnConversionMethod) -> [u8; DTC_LENGTH] {
+        let spn = method.encode(self.suspect_parameter_number);
+        let spn_conversion_method = (method != SpnConversionMethod::Version4) as u8;
+
+        [
+            spn[0],
+            spn[1],
+            spn[2] | u8::from(self.failure_mode_identifier),
+            spn_conversion_method << 7 | (self.occurrence_count & 0x7F),
+        ]
+    }
+}
+
+impl core::fmt::Display for Dtc {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "Suspect Parameter Number: {}, Failure Mode Identifier: {}, SPN Conversion Method: {}, Occurrence Count: {}",
+            self.suspect_parameter_number,
+            self.failure_mode_identifier,
+            self.spn_conversion_method,
+            self.occurrence_count
+        )
+    }
+}
+
+/// Diagnostic lamp status.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct Lamps {
+    pub protect_lamp: Option<LampStatus>,
+    pub amber_warning_lamp: Option<LampStatus>,
+    pub red_stop_lamp: Option<LampStatus>,
+    pub malfunction_indicator_lamp: Option<LampStatus>,
+    pub protect_lamp_flash: Option<FlashStatus>,
+    pub amber_warning_lamp_flash: Option<FlashStatus>,
+    pub red_stop_lamp_flash: Option<FlashStatus>,
+    pub malfunction_indicator_lamp_flash: Option<FlashStatus>,
+}
+
+impl Lamps {
+    pub fn from_pdu(pdu: &[u8]) -> Self {
+        Self {
+            protect_lamp: LampStatus::from_value(pdu[0]),
+            amber_warning_lamp: LampStatus::from_value(pdu[0] >> 2),
+            red_stop_lamp: LampStatus::from_value(pdu[0] >> 4),
+            malfunction_indicator_lamp: LampStatus::from_value(pdu[0] >> 6),
+            protect_lamp_flash: FlashStatus::from_value(pdu[1]),
+            amber_warning_lamp_flash: FlashStatus::from_value(pdu[1] >> 2),
+            red_stop_lamp_flash: FlashStatus::from_value(pdu[1] >> 4),
+            malfunction_indicator_lamp_flash: FlashStatus::from_value(pdu[1] >> 6),
+        }
+    }
+
+    pub fn to_pdu(&self) -> [u8; 2] {
+        [
+            LampStatus::to_value(self.protect_lamp)
+                | LampStatus::to_value(self.amber_warning_lamp) << 2
+                | LampStatus::to_value(self.red_stop_lamp) << 4
+                | LampStatus::to_value(self.malfunction_indicator_lamp) << 6,
+            FlashStatus::to_value(self.protect_lamp_flash)
+                | FlashStatus::to_value(self.amber_warning_lamp_flash) << 2
+                | FlashStatus::to_value(self.red_stop_lamp_flash) << 4
+                | FlashStatus::to_value(self.malfunction_indicator_lamp_flash) << 6,
+        ]
+    }
+}
+
+/// Diagnostic message with lamp status and a list of DTCs.
+///
+/// The list holds up to `N` DTCs. A message with at most one DTC fits in a single frame,
+/// a message with more DTCs is sent with the transport protocol. The same layout is used by
+/// DM1 and the other DTC lists.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct DiagnosticMessage<const N: usize> {
+    pub lamps: Lamps,
+    dtc: [Dtc; N],
+    dtc_count: usize,
+}
+
+impl<const N: usize> DiagnosticMessage<N> {
+    pub fn new(lamps: Lamps) -> Self {
+        Self {
+            lamps,
+            dtc: [Dtc {
+                suspect_parameter_number: 0,
+                failure_mode_identifier: Fmi::AboveNormalMostSevere,
+                spn_conversion_method: 0,
+                occurrence_count: 0,
+            }; N],
+            dtc_count: 0,
+        }
+    }
+
+    /// Add a DTC to the list.
+    ///
+    /// Returns the DTC back if the list is full.
+    pub fn push(&mut self, dtc: Dtc) -> Result<(), Dtc> {
+        if self.dtc_count == N {
+            return Err(dtc);
+        }
+
+        self.dtc[self.dtc_count] = dtc;
+        self.dtc_count += 1;
+
+        Ok(())
+    }
+
+    /// Returns a slice of the DTCs.
+    pub fn dtc(&self) -> &[Dtc] {
+        &self.dtc[..self.dtc_count]
+    }
+
+    /// Returns the number of DTCs.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.dtc_count
+    }
+
+    /// Returns `true` if there are no DTCs.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.dtc_count == 0
+    }
+
+    /// Returns `true` if the message must be sent with the transport protocol.
+    #[inline]
+    pub fn is_multi_packet(&self) -> bool {
+        self.dtc_count > 1
+    }
+
+    /// Returns the length of the encoded message.
+    pub fn pdu_length(&self) -> usize {
+        if self.is_multi_packet() {
+            2 + self.dtc_count * DTC_LENGTH
+        } else {
+            PDU_MAX_LENGTH
+        }
+    }
+
+    /// Decode the message from a single frame or a reassembled transport payload.
+    ///
+    /// An all zero DTC indicates there are no DTCs. DTCs beyond the capacity of the list are
+    /// dropped. Returns `None` if the PDU is shorter than the lamp status.
+    pub fn from_pdu(pdu: &[u8]) -> Option<Self> {
+        if pdu.len() < 2 {
+            return None;
+        }
+
+        let mut message = Self::new(Lamps::from_pdu(pdu));
+
+        for chunk in pdu[2..].chunks_exact(DTC_LENGTH) {
+            if chunk == [0; DTC_LENGTH] || chunk == [PDU_NOT_AVAILABLE; DTC_LENGTH] {
+                continue;
+            }
+
+            if message.push(Dtc::from_pdu(chunk)).is_err() {
+                break;
+            }
+        }
+
+        Some(message)
+    }
+
+    /// Encode the message into the buffer.
+    ///
+    /// Returns the number of bytes written, or `None` if the buffer is too small. A message
+    /// without DTCs carries an all zero DTC. The single frame message is padded to 8 bytes.
+    pub fn copy_to_slice(&self, pdu: &mut [u8]) -> Option<usize> {
+        let length = self.pdu_length();
+        let pdu = pdu.get_mut(..length)?;
+
+        pdu.fill(PDU_NOT_AVAILABLE);
+        pdu[..2].copy_from_slice(&self.lamps.to_pdu());
+
+        if self.is_empty() {
+            pdu[2..6].fill(0);
+        }
+
+        for (dtc, chunk) in self.dtc().iter().zip(pdu[2..].chunks_exact_mut(DTC_LENGTH)) {
+            chunk.copy_from_slice(&dtc.to_pdu());
+        }
+
+        Some(length)
+    }
+
+    /// Encode the message into a single frame PDU.
+    ///
+    /// Only the first DTC is encoded.
+    pub fn to_pdu(&self) -> [u8; PDU_MAX_LENGTH] {
+        let mut pdu = [PDU_NOT_AVAILABLE; PDU_MAX_LENGTH];
+
+        pdu[..2].copy_from_slice(&self.lamps.to_pdu());
+        match self.dtc().first() {
+            Some(dtc) => pdu[2..6].copy_from_slice(&dtc.to_pdu()),
+            None => pdu[2..6].fill(0),
+        }
+
+        pdu
+    }
+}
+
+/// Active diagnostic trouble codes (DM1).
+pub type ActiveDiagnosticTroubleCodes<const N: usize> = DiagnosticMessage<N>;
+
+/// Previously active diagnostic trouble codes (DM2).
+pub type PreviouslyActiveDiagnosticTroubleCodes<const N: usize> = DiagnosticMessage<N>;
+
+/// Create a request for the previously active DTCs (DM2).
+pub fn request_previously_active(da: u8, sa: u8) -> Frame {
+    protocol::request(da, sa, PGN::DiagnosticMessage2)
+}
+
+/// Create a request to clear the previously active DTCs (DM3).
+///
+/// The ECU responds with an acknowledgement, see `clear_response`.
+pub fn clear_previously_active(da: u8, sa: u8) -> Frame {
+    protocol::request(da, sa, PGN::DiagnosticMessage3)
+}
+
+/// Emission related active diagnostic trouble codes (DM12).
+pub type EmissionRelatedActiveDiagnosticTroubleCodes<const N: usize> = DiagnosticMessage<N>;
+
+/// Create a request for the emission related active DTCs (DM12).
+pub fn request_emission_related_active(da: u8, sa: u8) -> Frame {
+    protocol::request(da, sa, PGN::DiagnosticMessage12)
+}
+
+/// Create a request to clear the active DTCs (DM11).
+///
+/// The ECU responds with an acknowledgement, see `clear_response`.
+pub fn clear_active(da: u8, sa: u8) -> Frame {
+    protocol::request(da, sa, PGN::DiagnosticMessage11)
+}
+
+/// Create the acknowledgement of a clear request (DM3 or DM11).
+pub fn acknowledge_clear(sa: u8, pgn: PGN) -> Frame {
+    protocol::acknowledgement(sa, pgn)
+}
+
+/// Interpret an acknowledgement frame as a response to a clear request for the PGN.
+///
+/// Returns `None` if the frame is not an acknowledgement for the PGN.
+pub fn clear_response(frame: &Frame, pgn: PGN) -> Option<AcknowledgementControl> {
+    let acknowledgement = protocol::Acknowledgement::from_frame(frame)?;
+    if acknowledgement.pgn != pgn {
+        return None;
+    }
+
+    Some(acknowledgement.control)
+}
+
+/// Minimum length of a freeze frame, excluding the length byte.
+const FREEZE_FRAME_MIN_LENGTH: usize = 12;
+
+/// Freeze frame (DM4).
+///
+/// A freeze frame holds the conditions at the time a DTC was stored. The required parameters
+/// are followed by manufacturer specific SPN data.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FreezeFrame<'a> {
+    pub dtc: Dtc,
+    pub engine_torque_mode: Option<EngineTorqueMode>,
+    /// Boost pressure in kPa.
+    pub boost_pressure: Option<u16>,
+    /// Engine speed in rpm.
+    pub engine_speed: Option<u16>,
+    /// Engine percent load at current speed.
+    pub engine_load: Option<u8>,
+    /// Engine coolant temperature in degrees Celsius.
+    pub engine_coolant_temperature: Option<i16>,
+    /// Wheel-based vehicle speed in km/h.
+    pub vehicle_speed: Option<u8>,
+    /// Manufacturer specific SPN data.
+    pub manufacturer_data: &'a [u8],
+}
+
+impl<'a> FreezeFrame<'a> {
+    /// Decode a freeze frame, excluding the length byte.
+    ///
+    /// Returns `None` if the freeze frame is shorter than the required parameters.
+    pub fn from_pdu(pdu: &'a [u8]) -> Option<Self> {
+        if pdu.len() < FREEZE_FRAME_MIN_LENGTH {
+            return None;
+        }
+
+        Some(Self {
+            dtc: Dtc::from_pdu(&pdu[..DTC_LENGTH]),
+            engine_torque_mode: EngineTorqueMode::from_value(pdu[4]),
+            boost_pressure: slots::pressure6::dec(pdu[5]),
+            engine_speed: slots::rotational_velocity::dec([pdu[6], pdu[7]]),
+            engine_load: slots::position_level3::dec(pdu[8]),
+            engine_coolant_temperature: slots::temperature3::dec(pdu[9]),
+            vehicle_speed: slots::speed::dec([pdu[10], pdu[11]]),
+            manufacturer_data: &pdu[FREEZE_FRAME_MIN_LENGTH..],
+        })
+    }
+
+    /// Encode the freeze frame into the buffer, including the length byte.
+    ///
+    /// Returns the number of bytes written, or `None` if the buffer is too small or the
+    /// freeze frame is too long.
+    pub fn copy_to_slice(&self, pdu: &mut [u8]) -> Option<usize> {
+        let length = FREEZE_FRAME_MIN_LENGTH + self.manufacturer_data.len();
+        let pdu = pdu.get_mut(..length + 1)?;
+
+        pdu[0] = u8::try_from(length).ok()?;
+        pdu[1..5].copy_from_slice(&self.dtc.to_pdu());
+        pdu[5] = 0xF0 | EngineTorqueMode::to_value(self.engine_torque_mode);
+        pdu[6] = slots::pressure6::enc(self.boost_pressure);
+        pdu[7..9].copy_from_slice(&slots::rotational_velocity::enc(self.engine_speed));
+        pdu[9] = slots::position_level3::enc(self.engine_load);
+        pdu[10] = slots::temperature3::enc(self.engine_coolant_temperature);
+        pdu[11..13].copy_from_slice(&slots::speed::enc(self.vehicle_speed));
+        pdu[13..].copy_from_slice(self.manufacturer_data);
+
+        Some(length + 1)
+    }
+}
+
+/// Iterator over the freeze frames in a DM4 message.
+///
+/// Every freeze frame is preceded by its length. The iterator stops at the first freeze frame
+/// which is truncated or shorter than the required parameters.
+pub struct FreezeFrames<'a> {
+    pdu: &'a [u8],
+}
+
+impl<'a> FreezeFrames<'a> {
+    /// Decode the freeze frames from a single frame or a reassembled transport payload.
+    pub fn from_pdu(pdu: &'a [u8]) -> Self {
+        Self { pdu }
+    }
+}
+
+impl<'a> Iterator for FreezeFrames<'a> {
+    type Item = FreezeFrame<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (&length, pdu) = self.pdu.split_first()?;
+
+        let freeze_frame = pdu.get(..length as usize).and_then(FreezeFrame::from_pdu);
+
+        self.pdu = match freeze_frame {
+            Some(_) => &pdu[length as usize..],
+            None => &[],
+        };
+
+        freeze_frame
+    }
+}
+
+/// OBD compliance of the ECU.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ObdCompliance {
+    /// OBD II (California ARB).
+    ObdIi,
+    /// OBD (Federal, EPA).
+    Obd,
+    /// OBD and OBD II.
+    ObdAndObdIi,
+    /// OBD I.
+    ObdI,
+    /// Not intended to meet OBD II requirements.
+    NotObdIi,
+    /// EOBD (Europe).
+    Eobd,
+    /// EOBD and OBD II.
+    EobdAndObdIi,
+    /// EOBD and OBD.
+    EobdAndObd,
+    /// EOBD, OBD and OBD II.
+    EobdObdAndObdIi,
+    /// JOBD (Japan).
+    Jobd,
+    /// JOBD and OBD II.
+    JobdAndObdIi,
+    /// JOBD and EOBD.
+    JobdAndEobd,
+    /// JOBD, EOBD and OBD II.
+    JobdEobdAndObdIi,
+    /// Heavy duty vehicles (EURO IV) B1.
+    EuroIvB1,
+    /// Heavy duty vehicles (EURO V) B2.
+    EuroVB2,
+    /// Heavy duty vehicles (EURO EEC) C.
+    EuroEecC,
+    /// Engine manufacturer diagnostics (EMD).
+    Emd,
+    /// Engine manufacturer diagnostics enhanced (EMD+).
+    EmdPlus,
+    /// Heavy duty OBD partial (HD OBD P).
+    HdObdPartial,
+    /// Heavy duty OBD (HD OBD).
+    HdObd,
+    /// World wide harmonized OBD (WWH OBD).
+    WwhObd,
+    /// Any other compliance value.
+    Other(u8),
+}
+
+impl ObdCompliance {
+    pub fn from_value(value: u8) -> Option<Self> {
+        match value {
+            1 => Some(Self::ObdIi),
+            2 => Some(Self::Obd),
+            3 => Some(Self::ObdAndObdIi),
+            4 => Some(Self::ObdI),
+            5 => Some(Self::NotObdIi),
+            6 => Some(Self::Eobd),
+            7 => Some(Self::EobdAndObdIi),
+            8 => Some(Self::EobdAndObd),
+            9 => Some(Self::EobdObdAndObdIi),
+            10 => Some(Self::Jobd),
+            11 => Some(Self::JobdAndObdIi),
+            12 => Some(Self::JobdAndEobd),
+            13 => Some(Self::JobdEobdAndObdIi),
+            14 => Some(Self::EuroIvB1),
+            15 => Some(Self::EuroVB2),
+            16 => Some(Self::EuroEecC),
+            17 => Some(Self::Emd),
+            18 => Some(Self::EmdPlus),
+            19 => Some(Self::HdObdPartial),
+            20 => Some(Self::HdObd),
+            21 => Some(Self::WwhObd),
+            PDU_ERROR | PDU_NOT_AVAILABLE => None,
+            value => Some(Self::Other(value)),
+        }
+    }
+
+    pub fn to_value(compliance: Option<Self>) -> u8 {
+        match compliance {
+            Some(Self::ObdIi) => 1,
+            Some(Self::Obd) => 2,
+            Some(Self::ObdAndObdIi) => 3,
+            Some(Self::ObdI) => 4,
+            Some(Self::NotObdIi) => 5,
+            Some(Self::Eobd) => 6,
+            Some(Self::EobdAndObdIi) => 7,
+            Some(Self::EobdAndObd) => 8,
+            Some(Self::EobdObdAndObdIi) => 9,
+            Some(Self::Jobd) => 10,
+            Some(Self::JobdAndObdIi) => 11,
+            Some(Self::JobdAndEobd) => 12,
+            Some(Self::JobdEobdAndObdIi) => 13,
+            Some(Self::EuroIvB1) => 14,
+            Some(Self::EuroVB2) => 15,
+            Some(Self::EuroEecC) => 16,
+            Some(Self::Emd) => 17,
+            Some(Self::EmdPlus) => 18,
+            Some(Self::HdObdPartial) => 19,
+            Some(Self::HdObd) => 20,
+            Some(Self::WwhObd) => 21,
+            Some(Self::Other(value)) => value,
+            None => PDU_NOT_AVAILABLE,
+        }
+    }
+}
+
+/// Continuously monitored systems.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct ContinuouslyMonitoredSystems {
+    pub misfire: bool,
+    pub fuel_system: bool,
+    pub comprehensive_component: bool,
+}
+
+impl ContinuouslyMonitoredSystems {
+    pub fn from_value(value: u8) -> Self {
+        Self {
+            misfire: slots::bit_from_value(value),
+            fuel_system: slots::bit_from_value(value >> 1),
+            comprehensive_component: slots::bit_from_value(value >> 2),
+        }
+    }
+
+    pub fn to_value(systems: Self) -> u8 {
+        slots::bit_to_value(systems.misfire)
+            | slots::bit_to_value(systems.fuel_system) << 1
+            | slots::bit_to_value(systems.comprehensive_component) << 2
+    }
+}
+
+/// Non-continuously monitored systems.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct NonContinuouslyMonitoredSystems {
+    pub catalyst: bool,
+    pub heated_catalyst: bool,
+    pub evaporative_system: bool,
+    pub secondary_air_system: bool,
+    pub air_conditioning_system_refrigerant: bool,
+    pub exhaust_gas_sensor: bool,
+    pub exhaust_gas_sensor_heater: bool,
+    pub egr_system: bool,
+    pub cold_start_aid_system: bool,
+    pub boost_pressure_control_system: bool,
+    pub diesel_particulate_filter: bool,
+    pub nox_converting_catalyst: bool,
+    pub nmhc_converting_catalyst: bool,
+}
+
+impl NonContinuouslyMonitoredSystems {
+    pub fn from_value(value: [u8; 2]) -> Self {
+        Self {
+            catalyst: slots::bit_from_value(value[0]),
+            heated_catalyst: slots::bit_from_value(value[0] >> 1),
+            evaporative_system: slots::bit_from_value(value[0] >> 2),
+            secondary_air_system: slots::bit_from_value(value[0] >> 3),
+            air_conditioning_system_refrigerant: slots::bit_from_value(value[0] >> 4),
+            exhaust_gas_sensor: slots::bit_from_value(value[0] >> 5),
+            exhaust_gas_sensor_heater: slots::bit_from_value(value[0] >> 6),
+            egr_system: slots::bit_from_value(value[0] >> 7),
+            cold_start_aid_system: slots::bit_from_value(value[1]),
+            boost_pressure_control_system: slots::bit_from_value(value[1] >> 1),
+            diesel_particulate_filter: slots::bit_from_value(value[1] >> 2),
+            nox_converting_catalyst: slots::bit_from_value(value[1] >> 3),
+            nmhc_converting_catalyst: slots::bit_from_value(value[1] >> 4),
+        }
+    }
+
+    pub fn to_value(systems: Self) -> [u8; 2] {
+        [
+            slots::bit_to_value(systems.catalyst)
+                | slots::bit_to_value(systems.heated_catalyst) << 1
+                | slots::bit_to_value(systems.evaporative_system) << 2
+                | slots::bit_to_value(systems.secondary_air_system) << 3
+                | slots::bit_to_value(systems.air_conditioning_system_refrigerant) << 4
+                | slots::bit_to_value(systems.exhaust_gas_sensor) << 5
+                | slots::bit_to_value(systems.exhaust_gas_sensor_heater) << 6
+                | slots::bit_to_value(systems.egr_system) << 7,
+            slots::bit_to_value(systems.cold_start_aid_system)
+                | slots::bit_to_value(systems.boost_pressure_control_system) << 1
+                | slots::bit_to_value(systems.diesel_particulate_filter) << 2
+                | slots::bit_to_value(systems.nox_converting_catalyst) << 3
+                | slots::bit_to_value(systems.nmhc_converting_catalyst) << 4
+                | 0xE0,
+        ]
+    }
+}
+
+/// Diagnostic readiness 1 (DM5).
+///
+/// Monitor support is `true` if the monitor is supported. Monitor status is `true` if the
+/// monitor is not complete.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct DiagnosticReadiness1 {
+    pub active_trouble_codes: Option<u8>,
+    pub previously_active_trouble_codes: Option<u8>,
+    pub obd_compliance: Option<ObdCompliance>,
+    pub continuously_monitored_systems_support: ContinuouslyMonitoredSystems,
+    pub continuously_monitored_systems_status: ContinuouslyMonitoredSystems,
+    pub non_continuously_monitored_systems_support: NonContinuouslyMonitoredSystems,
+    pub non_continuously_monitored_systems_status: NonContinuouslyMonitoredSystems,
+}
+
+impl DiagnosticReadiness1 {
+    /// Decode the message.
+    ///
+    /// Returns `None` if the PDU is shorter than 8 bytes.
+    pub fn from_pdu(pdu: &[u8]) -> Option<Self> {
+        if pdu.len() < PDU_MAX_LENGTH {
+            return None;
+        }
+
+        Some(Self {
+            active_trouble_codes: slots::count::dec(pdu[0]),
+            previously_active_trouble_codes: slots::count::dec(pdu[1]),
+            obd_compliance: ObdCompliance::from_value(pdu[2]),
+            continuously_monitored_systems_support: ContinuouslyMonitoredSystems::from_value(
+                pdu[3],
+            ),
+            continuously_monitored_systems_status: ContinuouslyMonitoredSystems::from_value(
+                pdu[3] >> 4,
+            ),
+            non_continuously_monitored_systems_support: NonContinuouslyMonitoredSystems::from_value(
+                [pdu[4], pdu[5]],
+            ),
+            non_continuously_monitored_systems_status: NonContinuouslyMonitoredSystems::from_value(
+                [pdu[6], pdu[7]],
+            ),
+        })
+    }
+
+    pub fn to_pdu(&self) -> [u8; 8] {
+        let support = NonContinuouslyMonitoredSystems::to_value(
+            self.non_continuously_monitored_systems_support,
+        );
+        let status = NonContinuouslyMonitoredSystems::to_value(
+            self.non_continuously_monitored_systems_status,
+        );
+
+        [
+            slots::count::enc(self.active_trouble_codes),
+            slots::count::enc(self.previously_active_trouble_codes),
+            ObdCompliance::to_value(self.obd_compliance),
+            ContinuouslyMonitoredSystems::to_value(self.continuously_monitored_systems_support)
+                | ContinuouslyMonitoredSystems::to_value(
+                    self.continuously_monitored_systems_status,
+                ) << 4
+                | 0x88,
+            support[0],
+            support[1],
+            status[0],
+            status[1],
+        ]
+    }
+}
+
+/// Write a count, or "N/A" if the count is not available.
+fn fmt_count<T: core::fmt::Display>(
+    f: &mut core::fmt::Formatter,
+    count: Option<T>,
+) -> core::fmt::Result {
+    match count {
+        Some(count) => write!(f, "{}", count),
+        None => write!(f, "N/A"),
+    }
+}
+
+impl core::fmt::Display for DiagnosticReadiness1 {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Active Trouble Codes: ")?;
+        fmt_count(f, self.active_trouble_codes)?;
+        write!(f, ", Previously Active Trouble Codes: ")?;
+        fmt_count(f, self.previously_active_trouble_codes)?;
+        write!(f, ", OBD Compliance: {:?}", self.obd_compliance)
+    }
+}
+
+/// Time after the last stop or hold signal at which broadcasts resume.
+pub const BROADCAST_HOLD_TIMEOUT: Duration = Duration::from_secs(6);
+
+/// Broadcast command for a network.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BroadcastCommand {
+    /// Stop broadcast.
+    Stop,
+    /// Start broadcast.
+    Start,
+}
+
+impl BroadcastCommand {
+    pub fn from_value(value: u8) -> Option<Self> {
+        match value & 0b11 {
+            0b00 => Some(Self::Stop),
+            0b01 => Some(Self::Start),
+            _ => None,
+        }
+    }
+
+    pub fn to_value(command: Option<Self>) -> u8 {
+        match command {
+            Some(Self::Stop) => 0b00,
+            Some(Self::Start) => 0b01,
+            None => 0b11,
+        }
+    }
+}
+
+/// Hold signal.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HoldSignal {
+    /// All devices keep their broadcast state.
+    AllDevices,
+    /// Only devices whose broadcast state has been modified keep their broadcast state.
+    ModifiedDevices,
+}
+
+impl HoldSignal {
+    pub fn from_value(value: u8) -> Option<Self> {
+        match value & 0b1111 {
+            0b0000 => Some(Self::AllDevices),
+            0b0001 => Some(Self::ModifiedDevices),
+            _ => None,
+        }
+    }
+
+    pub fn to_value(signal: Option<Self>) -> u8 {
+        match signal {
+            Some(Self::AllDevices) => 0b0000,
+            Some(Self::ModifiedDevices) => 0b0001,
+            None => 0b1111,
+        }
+    }
+}
+
+/// Suspend signal.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SuspendSignal {
+    /// Indefinite suspension of all broadcasts.
+    IndefiniteAll,
+    /// Indefinite suspension of some broadcasts.
+    IndefinitePartial,
+    /// Temporary suspension of all broadcasts.
+    TemporaryAll,
+    /// Temporary suspension of some broadcasts.
+    TemporaryPartial,
+    /// Broadcasts are resuming.
+    Resuming,
+}
+
+impl SuspendSignal {
+    pub fn from_value(value: u8) -> Option<Self> {
+        match value & 0b1111 {
+            0b0000 => Some(Self::IndefiniteAll),
+            0b0001 => Some(Self::IndefinitePartial),
+            0b0010 => Some(Self::TemporaryAll),
+            0b0011 => Some(Self::TemporaryPartial),
+            0b1110 => Some(Self::Resuming),
+            _ => None,
+        }
+    }
+
+    pub fn to_value(signal: Option<Self>) -> u8 {
+        match signal {
+            Some(Self::IndefiniteAll) => 0b0000,
+            Some(Self::IndefinitePartial) => 0b0001,
+            Some(Self::TemporaryAll) => 0b0010,
+            Some(Self::TemporaryPartial) => 0b0011,
+            Some(Self::Resuming) => 0b1110,
+            None => 0b1111,
+        }
+    }
+}
+
+/// Stop start broadcast (DM13).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct StopStartBroadcast {
+    /// Network on which the message is received.
+    pub current_data_link: Option<BroadcastCommand>,
+    pub j1587: Option<BroadcastCommand>,
+    pub j1922: Option<BroadcastCommand>,
+    pub j1939_network1: Option<BroadcastCommand>,
+    pub j1939_network2: Option<BroadcastCommand>,
+    pub iso9141: Option<BroadcastCommand>,
+    pub j1850: Option<BroadcastCommand>,
+    pub manufacturer_specific_port: Option<BroadcastCommand>,
+    pub j1939_network3: Option<BroadcastCommand>,
+    pub hold_signal: Option<HoldSignal>,
+    pub suspend_signal: Option<SuspendSignal>,
+    /// Suspend duration in seconds.
+    pub suspend_duration: Option<u16>,
+}
+
+impl StopStartBroadcast {
+    /// Decode the message.
+    ///
+    /// Returns `None` if the PDU is shorter than 8 bytes.
+    pub fn from_pdu(pdu: &[u8]) -> Option<Self> {
+        if pdu.len() < PDU_MAX_LENGTH {
+            return None;
+        }
+
+        let suspend_duration = u16::from_le_bytes([pdu[4], pdu[5]]);
+
+        Some(Self {
+            current_data_link: BroadcastCommand::from_value(pdu[0]),
+            j1587: BroadcastCommand::from_value(pdu[0] >> 2),
+            j1922: BroadcastCommand::from_value(pdu[0] >> 4),
+            j1939_network1: BroadcastCommand::from_value(pdu[0] >> 6),
+            j1939_network2: BroadcastCommand::from_value(pdu[1]),
+            iso9141: BroadcastCommand::from_value(pdu[1] >> 2),
+            j1850: BroadcastCommand::from_value(pdu[1] >> 4),
+            manufacturer_specific_port: BroadcastCommand::from_value(pdu[1] >> 6),
+            j1939_network3: BroadcastCommand::from_value(pdu[2]),
+            hold_signal: HoldSignal::from_value(pdu[3] >> 4),
+            suspend_signal: SuspendSignal::from_value(pdu[3]),
+            suspend_duration: if suspend_duration == u16::MAX {
+                None
+            } else {
+                Some(suspend_duration)
+            },
+        })
+    }
+
+    pub fn to_pdu(&self) -> [u8; 8] {
+        let suspend_duration = self.suspend_duration.unwrap_or(u16::MAX).to_le_bytes();
+
+        [
+            BroadcastCommand::to_value(self.current_data_link)
+                | BroadcastCommand::to_value(self.j1587) << 2
+                | BroadcastCommand::to_value(self.j1922) << 4
+                | BroadcastCommand::to_value(self.j1939_network1) << 6,
+            BroadcastCommand::to_value(self.j1939_network2)
+                | BroadcastCommand::to_value(self.iso9141) << 2
+                | BroadcastCommand::to_value(self.j1850) << 4
+                | BroadcastCommand::to_value(self.manufacturer_specific_port) << 6,
+            BroadcastCommand::to_value(self.j1939_network3) | 0xFC,
+            HoldSignal::to_value(self.hold_signal) << 4
+                | SuspendSignal::to_value(self.suspend_signal),
+            suspend_duration[0],
+            suspend_duration[1],
+            PDU_NOT_AVAILABLE,
+            PDU_NOT_AVAILABLE,
+        ]
+    }
+}
+
+impl core::fmt::Display for StopStartBroadcast {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "Current Data Link: {:?}, J1939 Network 1: {:?}, Hold Signal: {:?}, Suspend Signal: {:?}, Suspend Duration: ",
+            self.current_data_link,
+            self.j1939_network1,
+            self.hold_signal,
+            self.suspend_signal,
+        )?;
+
+        match self.suspend_duration {
+            Some(duration) => write!(f, "{}s", duration),
+            None => write!(f, "N/A"),
+        }
+    }
+}
+
+/// Broadcast suspension state of a node.
+///
+/// The state follows the broadcast command for the current data link. Broadcasts are
+/// suspended on a stop command and resume on a start command, when the suspend duration has
+/// elapsed, or when no stop or hold signal was received for 6 seconds.
+pub struct BroadcastSuspension {
+    suspended: bool,
+    hold: Timer,
+    duration: Timer,
+}
+
+impl BroadcastSuspension {
+    pub fn new() -> Self {
+        Self {
+            suspended: false,
+            hold: Timer::new(),
+            duration: Timer::new(),
+        }
+    }
+
+    /// Returns `true` if broadcasts are suspended.
+    #[inline]
+    pub fn is_suspended(&self) -> bool {
+        self.suspended
+    }
+
+    fn resume(&mut self) {
+        self.suspended = false;
+        self.hold.disarm();
+        self.duration.disarm();
+    }
+
+    /// Process a stop start broadcast message.
+    pub fn from_message(&mut self, message: &StopStartBroadcast) {
+        match message.current_data_link {
+            Some(BroadcastCommand::Stop) => {
+                self.suspended = true;
+                self.hold.arm(BROADCAST_HOLD_TIMEOUT);
+
+                match message.suspend_duration {
+                    Some(duration) => self.duration.arm(Duration::from_secs(duration as u64)),
+                    None => self.duration.disarm(),
+                }
+            }
+            Some(BroadcastCommand::Start) => self.resume(),
+            None => {
+                if self.suspended && message.hold_signal.is_some() {
+                    self.hold.arm(BROADCAST_HOLD_TIMEOUT);
+                }
+            }
+        }
+    }
+
+    /// Advance the state to the current time.
+    ///
+    /// Returns `true` if broadcasts are suspended.
+    pub fn tick(&mut self, now: Duration) -> bool {
+        let hold_expired = self.hold.is_expired(now);
+        let duration_expired = self.duration.is_expired(now);
+
+        if hold_expired || duration_expired {
+            self.resume();
+        }
+
+        self.suspended
+    }
+}
+
+impl Default for BroadcastSuspension {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Maximum number of bytes in a binary data transfer (DM16).
+pub const BINARY_DATA_MAX_LENGTH: usize = 255;
+
+/// Time to wait for the ECU to respond to a memory access request.
+pub const MEMORY_ACCESS_TIMEOUT: Duration = Duration::from_millis(1250);
+
+/// Memory access command.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MemoryAccessCommand {
+    /// Erase memory.
+    Erase,
+    /// Read memory.
+    Read,
+    /// Write memory.
+    Write,
+    /// Request the status of the operation.
+    StatusRequest,
+    /// Operation completed.
+    OperationCompleted,
+    /// Operation failed.
+    OperationFailed,
+    /// Boot load.
+    BootLoad,
+    /// Generate the error detection and correction parameter (EDCP).
+    EdcpGeneration,
+}
+
+impl MemoryAccessCommand {
+    pub fn from_value(value: u8) -> Self {
+        match value & 0b111 {
+            0 => Self::Erase,
+            1 => Self::Read,
+            2 => Self::Write,
+            3 => Self::StatusRequest,
+            4 => Self::OperationCompleted,
+            5 => Self::OperationFailed,
+            6 => Self::BootLoad,
+            _ => Self::EdcpGeneration,
+        }
+    }
+
+    pub fn to_value(command: Self) -> u8 {
+        match command {
+            Self::Erase => 0,
+            Self::Read => 1,
+            Self::Write => 2,
+            Self::StatusRequest => 3,
+            Self::OperationCompleted => 4,
+            Self::OperationFailed => 5,
+            Self::BootLoad => 6,
+            Self::EdcpGeneration => 7,
+        }
+    }
+}
+
+/// Memory pointer type.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PointerType {
+    /// Pointer is a direct memory address.
+    Direct,
+    /// Pointer is a directed spatial address, such as an object identifier.
+    Spatial,
+}
+
+/// Memory access status.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MemoryAccessStatus {
+    /// Proceed with the operation.
+    Proceed,
+    /// ECU is busy, the client should keep waiting.
+    Busy,
+    /// Operation completed.
+    OperationCompleted,
+    /// Operation failed.
+    OperationFailed,
+}
+
+impl MemoryAccessStatus {
+    pub fn from_value(value: u8) -> Option<Self> {
+        match value & 0b111 {
+            0 => Some(Self::Proceed),
+            1 => Some(Self::Busy),
+            4 => Some(Self::OperationCompleted),
+            5 => Some(Self::OperationFailed),
+            _ => None,
+        }
+    }
+
+    pub fn to_value(status: Option<Self>) -> u8 {
+        match status {
+            Some(Self::Proceed) => 0,
+            Some(Self::Busy) => 1,
+            Some(Self::OperationCompleted) => 4,
+            Some(Self::OperationFailed) => 5,
+            None => 7,
+        }
+    }
+}
+
+/// Memory access request (DM14).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MemoryAccessRequest {
+    /// Number of bytes or objects, 11 bits.
+    pub length: u16,
+    pub pointer_type: PointerType,
+    pub command: MemoryAccessCommand,
+    /// Memory address or object identifier, 24 bits.
+    pub pointer: u32,
+    pub pointer_extension: u8,
+    /// Key computed from the seed of the ECU.
+    pub key: Option<u16>,
+}
+
+impl MemoryAccessRequest {
+    /// Returns `None` if the PDU is shorter than 8 bytes.
+    pub fn from_pdu(pdu: &[u8]) -> Option<Self> {
+        if pdu.len() < PDU_MAX_LENGTH {
+            return None;
+        }
+
+        let key = u16::from_le_bytes([pdu[6], pdu[7]]);
+
+        Some(Self {
+            length: u16::from(pdu[1] >> 5) << 8 | u16::from(pdu[0]),
+            pointer_type: if pdu[1] & 0b1_0000 == 0 {
+                PointerType::Direct
+            } else {
+                PointerType::Spatial
+            },
+            command: MemoryAccessCommand::from_value(pdu[1] >> 1),
+            pointer: u32::from_le_bytes([pdu[2], pdu[3], pdu[4], 0]),
+            pointer_extension: pdu[5],
+            key: if key == u16::MAX { None } else { Some(key) },
+        })
+    }
+
+    pub fn to_pdu(&self) -> [u8; 8] {
+        let pointer = self.pointer.to_le_bytes();
+        let key = self.key.unwrap_or(u16::MAX).to_le_bytes();
+        let pointer_type = match self.pointer_type {
+            PointerType::Direct => 0,
+            PointerType::Spatial => 1,
+        };
+
+        [
+            self.length as u8,
+            ((self.length >> 8) as u8 & 0b111) << 5
+                | pointer_type << 4
+                | MemoryAccessCommand::to_value(self.command) << 1
+                | 0b1,
+            pointer[0],
+            pointer[1],
+            pointer[2],
+            self.pointer_extension,
+            key[0],
+            key[1],
+        ]
+    }
+}
+
+impl core::fmt::Display for MemoryAccessRequest {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "Command: {:?}, Pointer Type: {:?}, Pointer: 0x{:02X}{:06X}, Length: {}",
+            self.command, self.pointer_type, self.pointer_extension, self.pointer, self.length
+        )
+    }
+}
+
+/// Memory access response (DM15).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MemoryAccessResponse {
+    /// Number of bytes or objects allowed, 11 bits.
+    pub length: u16,
+    pub status: Option<MemoryAccessStatus>,
+    /// Error indicator or error detection and correction parameter (EDCP), 24 bits.
+    pub edcp: Option<u32>,
+    pub edcp_extension: Option<u8>,
+    /// Seed for the key of the next request.
+    pub seed: Option<u16>,
+}
+
+impl MemoryAccessResponse {
+    /// Returns `None` if the PDU is shorter than 8 bytes.
+    pub fn from_pdu(pdu: &[u8]) -> Option<Self> {
+        if pdu.len() < PDU_MAX_LENGTH {
+            return None;
+        }
+
+        let edcp = u32::from_le_bytes([pdu[2], pdu[3], pdu[4], 0]);
+        let seed = u16::from_le_bytes([pdu[6], pdu[7]]);
+
+        Some(Self {
+            length: u16::from(pdu[1] >> 5) << 8 | u16::from(pdu[0]),
+            status: MemoryAccessStatus::from_value(pdu[1] >> 1),
+            edcp: if edcp == 0xFF_FFFF { None } else { Some(edcp) },
+            edcp_extension: if pdu[5] == PDU_NOT_AVAILABLE {
+                None
+            } else {
+                Some(pdu[5])
+            },
+            seed: if seed == u16::MAX { None } else { Some(seed) },
+        })
+    }
+
+    pub fn to_pdu(&self) -> [u8; 8] {
+        let edcp = self.edcp.unwrap_or(0xFF_FFFF).to_le_bytes();
+        let seed = self.seed.unwrap_or(u16::MAX).to_le_bytes();
+
+        [
+            self.length as u8,
+            ((self.length >> 8) as u8 & 0b111) << 5
+                | 0b1_0000
+                | MemoryAccessStatus::to_value(self.status) << 1
+                | 0b1,
+            edcp[0],
+            edcp[1],
+            edcp[2],
+            self.edcp_extension.unwrap_or(PDU_NOT_AVAILABLE),
+            seed[0],
+            seed[1],
+        ]
+    }
+}
+
+impl core::fmt::Display for MemoryAccessResponse {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "Status: {:?}, Length: {}, EDCP: {:?}, Seed: {:?}",
+            self.status, self.length, self.edcp, self.seed
+        )
+    }
+}
+
+/// Binary data transfer (DM16).
+///
+/// The data is preceded by the number of data bytes. Messages longer than a single frame are
+/// sent with the transport protocol.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BinaryDataTransfer<'a> {
+    pub data: &'a [u8],
+}
+
+impl<'a> BinaryDataTransfer<'a> {
+    /// Returns `None` if the PDU is shorter than the number of data bytes.
+    pub fn from_pdu(pdu: &'a [u8]) -> Option<Self> {
+        let length = *pdu.first()? as usize;
+
+        Some(Self {
+            data: pdu.get(1..length + 1)?,
+        })
+    }
+
+    pub fn copy_to_slice(&self, pdu: &mut [u8]) -> Option<usize> {
+        if self.data.len() > BINARY_DATA_MAX_LENGTH || pdu.len() < self.data.len() + 1 {
+            return None;
+        }
+
+        pdu[0] = self.data.len() as u8;
+        pdu[1..self.data.len() + 1].copy_from_slice(self.data);
+
+        Some(self.data.len() + 1)
+    }
+}
+
+/// Memory access error.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MemoryAccessError {
+    /// ECU did not respond in time.
+    Timeout,
+    /// ECU failed the operation, with the error indicator if provided.
+    OperationFailed(Option<u32>),
+    /// Binary data is inconsistent with its length.
+    BadLength,
+    /// Binary data transfer failed.
+    Transport(TransportError),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MemoryAccessClientState {
+    /// Request is pending.
+    Request,
+    /// Waiting for the ECU to respond to the request.
+    Response,
+    /// Waiting for the key to the seed of the ECU.
+    Seed(u16),
+    /// Transferring the binary data.
+    DataTransfer,
+    /// Waiting for the ECU to complete the operation.
+    Operation,
+    /// Operation completed acknowledgement is pending.
+    OperationCompleted,
+    /// Operation completed.
+    Complete,
+    /// Operation failed.
+    Failed(MemoryAccessError),
+}
+
+/// Memory access client.
+///
+/// The client sends a memory access request (DM14) to the ECU and follows the responses (DM15)
+/// of the ECU. A seed in the response must be answered with a key, see `key`. The binary data
+/// (DM16) of a read or write is transferred with the transport protocol if it does not fit in
+/// a single frame. Once the ECU completed the operation the client confirms with an operation
+/// completed request.
+///
+/// The data of a write is the binary data transfer PDU, see `BinaryDataTransfer::copy_to_slice`.
+pub struct MemoryAccessClient<'a> {
+    sa: u8,
+    da: u8,
+    request: MemoryAccessRequest,
+    data: &'a [u8],
+    transmitter: Option<ConnectionTransmitter<'a>>,
+    receiver: ConnectionReceiver,
+    buffer: [u8; BINARY_DATA_MAX_LENGTH],
+    buffer_length: usize,
+    abort: Option<Frame>,
+    state: MemoryAccessClientState,
+    timer: Timer,
+}
+
+impl<'a> MemoryAccessClient<'a> {
+    pub fn new(sa: u8, da: u8, request: MemoryAccessRequest) -> Self {
+        Self {
+            sa,
+            da,
+            request,
+            data: &[],
+            transmitter: None,
+            receiver: ConnectionReceiver::new(sa),
+            buffer: [PDU_NOT_AVAILABLE; BINARY_DATA_MAX_LENGTH],
+            buffer_length: 0,
+            abort: None,
+            state: MemoryAccessClientState::Request,
+            timer: Timer::new(),
+        }
+    }
+
+    /// Set the binary data transfer PDU to write.
+    pub fn with_data(mut self, data: &'a [u8]) -> Self {
+        self.data = data;
+        self
+    }
+
+    /// Returns the current state of the client.
+    #[inline]
+    pub fn state(&self) -> MemoryAccessClientState {
+        self.state
+    }
+
+    /// Returns `true` if the operation completed.
+    #[inline]
+    pub fn is_complete(&self) -> bool {
+        self.state == MemoryAccessClientState::Complete
+    }
+
+    /// Returns the binary data read from the ECU.
+    pub fn data(&self) -> &[u8] {
+        &self.buffer[..self.buffer_length]
+    }
+
+    /// Answer the seed of the ECU with the key.
+    ///
+    /// The request is sent again with the key.
+    pub fn key(&mut self, key: u16) {
+        if let MemoryAccessClientState::Seed(_) = self.state {
+            self.request.key = Some(key);
+            self.state = MemoryAccessClientState::Request;
+        }
+    }
+
+    fn frame(&self, pgn: PGN, pdu: &[u8]) -> Frame {
+        FrameBuilder::new(IdBuilder::from_pgn(pgn).sa(self.sa).da(self.da).build())
+            .copy_from_slice(pdu)
+            .set_len(PDU_MAX_LENGTH)
+            .build()
+    }
+
+    fn fail(&mut self, error: MemoryAccessError) -> MemoryAccessError {
+        self.state = MemoryAccessClientState::Failed(error);
+        self.transmitter = None;
+        self.timer.disarm();
+
+        error
+    }
+
+    fn proceed(&mut self) {
+        match self.request.command {
+            MemoryAccessCommand::Write => {
+                if self.data.len() > PDU_MAX_LENGTH {
+                    match ConnectionTransmitter::new(self.sa, self.da, PGN::DiagnosticMessage16)
+                        .with_data(self.data)
+                    {
+                        Ok(transmitter) => self.transmitter = Some(transmitter),
+                        Err(_) => {
+                            self.fail(MemoryAccessError::BadLength);
+                            return;
+                        }
+                    }
+                }
+                self.state = MemoryAccessClientState::DataTransfer;
+                self.timer.disarm();
+            }
+            MemoryAccessCommand::Read => {
+                self.state = MemoryAccessClientState::DataTransfer;
+                self.timer.arm(MEMORY_ACCESS_TIMEOUT);
+            }
+            _ => {
+                self.state = MemoryAccessClientState::Operation;
+                self.timer.arm(MEMORY_ACCESS_TIMEOUT);
+            }
+        }
+    }
+
+    fn receive(&mut self, pdu: &[u8]) -> Result<(), MemoryAccessError> {
+        let message = match BinaryDataTransfer::from_pdu(pdu) {
+            Some(message) => message,
+            None => return Err(self.fail(MemoryAccessError::BadLength)),
+        };
+
+        self.buffer[..message.data.len()].copy_from_slice(message.data);
+        self.buffer_length = message.data.len();
+        self.state = MemoryAccessClientState::Operation;
+        self.timer.arm(MEMORY_ACCESS_TIMEOUT);
+
+        Ok(())
+    }
+
+    /// Follow the transport session of the binary data.
+    fn update_transfer(&mut self) -> Result<(), MemoryAccessError> {
+        if let Some(transmitter) = &self.transmitter {
+            match transmitter.state() {
+                ConnectionTransmitterState::Complete => {
+                    self.transmitter = None;
+                    self.state = MemoryAccessClientState::Operation;
+                    self.timer.arm(MEMORY_ACCESS_TIMEOUT);
+                }
+                ConnectionTransmitterState::Aborted(reason) => {
+                    return Err(
+                        self.fail(MemoryAccessError::Transport(TransportError::Aborted(
+                            reason,
+                        ))),
+                    );
+                }
+                _ => {}
+            }
+        } else if self.receiver.is_complete() && self.receiver.pgn() == PGN::DiagnosticMessage16 {
+            let mut pdu = [0; BINARY_DATA_MAX_LENGTH + 1];
+            let length = self.receiver.len().min(pdu.len());
+            pdu[..length].copy_from_slice(&self.receiver.data()[..length]);
+
+            self.receiver = ConnectionReceiver::new(self.sa);
+            self.receive(&pdu[..length])?;
+        } else if let ConnectionReceiverState::Aborted(reason) = self.receiver.state() {
+            self.receiver = ConnectionReceiver::new(self.sa);
+            return Err(
+                self.fail(MemoryAccessError::Transport(TransportError::Aborted(
+                    reason,
+                ))),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Follow the transport session after it produced the frame.
+    ///
+    /// On error the frame is kept and sent with the next call to `next_frame` or `tick`.
+    fn finish_transfer(
+        &mut self,
+        frame: Option<Frame>,
+    ) -> Result<Option<Frame>, MemoryAccessError> {
+        match self.update_transfer() {
+            Ok(()) => Ok(frame),
+            Err(error) => {
+                if frame.is_some() {
+                    self.abort = frame;
+                }
+                Err(error)
+            }
+        }
+    }
+
+    /// Returns the next frame to send, if any.
+    ///
+    /// Fails if the transport session of the binary data was aborted. The abort frame is
+    /// returned with the next call.
+    pub fn next_frame(&mut self) -> Result<Option<Frame>, MemoryAccessError> {
+        if let Some(frame) = self.abort.take() {
+            return Ok(Some(frame));
+        }
+
+        match self.state {
+            MemoryAccessClientState::Request => {
+                self.state = MemoryAccessClientState::Response;
+                self.timer.arm(MEMORY_ACCESS_TIMEOUT);
+
+                Ok(Some(
+                    self.frame(PGN::DiagnosticMessage14, &self.request.to_pdu()),
+                ))
+            }
+            MemoryAccessClientState::DataTransfer => {
+                let frame = match &mut self.transmitter {
+                    Some(transmitter) => transmitter.next_frame(),
+                    None if self.request.command == MemoryAccessCommand::Write => {
+                        self.state = MemoryAccessClientState::Operation;
+                        self.timer.arm(MEMORY_ACCESS_TIMEOUT);
+
+                        return Ok(Some(self.frame(PGN::DiagnosticMessage16, self.data)));
+                    }
+                    None => self.receiver.next_frame(),
+                };
+
+                self.finish_transfer(frame)
+            }
+            MemoryAccessClientState::OperationCompleted => {
+                let request = MemoryAccessRequest {
+                    command: MemoryAccessCommand::OperationCompleted,
+                    key: None,
+                    ..self.request
+                };
+
+                self.state = MemoryAccessClientState::Complete;
+
+                Ok(Some(
+                    self.frame(PGN::DiagnosticMessage14, &request.to_pdu()),
+                ))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Advance the client to the current time.
+    ///
+    /// Returns the next frame to send. The client fails if the ECU did not respond within
+    /// `MEMORY_ACCESS_TIMEOUT`, or the binary data transfer timed out or was aborted. The abort
+    /// frame of a failed transfer is returned with the next call.
+    pub fn tick(&mut self, now: Duration) -> Result<Option<Frame>, MemoryAccessError> {
+        if self.timer.is_expired(now) {
+            return Err(self.fail(MemoryAccessError::Timeout));
+        }
+
+        if self.state == MemoryAccessClientState::DataTransfer {
+            let frame = match &mut self.transmitter {
+                Some(transmitter) => transmitter.tick(now),
+                None if self.request.command == MemoryAccessCommand::Write => None,
+                None => self.receiver.tick(now),
+            };
+
+            if let Some(frame) = self.finish_transfer(frame)? {
+                return Ok(Some(frame));
+            }
+        }
+
+        self.next_frame()
+    }
+
+    /// Process a frame from the ECU.
+    ///
+    /// Frames not addressed to this client are ignored. On error the client fails, a failed
+    /// transport session is aborted with the next frame.
+    pub fn from_frame(&mut self, frame: &Frame) -> Result<(), MemoryAccessError> {
+        if frame.id().source_address() != self.da
+            || frame.id().destination_address() != Some(self.sa)
+        {
+            return Ok(());
+        }
+
+        let pdu = frame.pdu();
+
+        match frame.id().pgn() {
+            PGN::TransportProtocolConnectionManagement | PGN::TransportProtocolDataTransfer
+                if self.state == MemoryAccessClientState::DataTransfer =>
+            {
+                let result = match &mut self.transmitter {
+                    Some(transmitter) => transmitter.from_frame(frame),
+                    None => {
+                        self.timer.disarm();
+                        self.receiver.from_frame(frame)
+                    }
+                };
+
+                match result {
+                    Ok(()) => self.update_transfer(),
+                    Err(TransportError::Aborted(reason)) => Err(self.fail(
+                        MemoryAccessError::Transport(TransportError::Aborted(reason)),
+                    )),
+                    Err(error) => {
+                        self.abort = Some(match &mut self.transmitter {
+                            Some(transmitter) => transmitter.abort(error.abort_reason()),
+                            None => self.receiver.abort(error.abort_reason()),
+                        });
+
+                        Err(self.fail(MemoryAccessError::Transport(error)))
+                    }
+                }
+            }
+            PGN::DiagnosticMessage15 => {
+                let response = match MemoryAccessResponse::from_pdu(pdu) {
+                    Some(response) => response,
+                    None => return Ok(()),
+                };
+
+                match (self.state, response.status) {
+                    (MemoryAccessClientState::Response, Some(MemoryAccessStatus::Proceed)) => {
+                        match response.seed {
+                            Some(seed) => {
+                                self.state = MemoryAccessClientState::Seed(seed);
+                                self.timer.disarm();
+                            }
+                            None => self.proceed(),
+                        }
+                    }
+                    (
+                        MemoryAccessClientState::Response | MemoryAccessClientState::Operation,
+                        Some(MemoryAccessStatus::Busy),
+                    ) => self.timer.arm(MEMORY_ACCESS_TIMEOUT),
+                    (
+                        MemoryAccessClientState::Response | MemoryAccessClientState::Operation,
+                        Some(MemoryAccessStatus::OperationCompleted),
+                    ) => {
+                        self.state = MemoryAccessClientState::OperationCompleted;
+                        self.timer.disarm();
+                    }
+                    (
+                        MemoryAccessClientState::Response
+                        | MemoryAccessClientState::DataTransfer
+                        | MemoryAccessClientState::Operation,
+                        Some(MemoryAccessStatus::OperationFailed),
+                    ) => {
+                        return Err(self.fail(MemoryAccessError::OperationFailed(response.edcp)));
+                    }
+                    _ => {}
+                }
+
+                Ok(())
+            }
+            PGN::DiagnosticMessage16
+                if self.state == MemoryAccessClientState::DataTransfer
+                    && self.request.command != MemoryAccessCommand::Write =>
+            {
+                self.receive(pdu)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Number of bytes in a calibration information record.
+pub const CALIBRATION_LENGTH: usize = 20;
+/// Number of bytes in a calibration identification.
+pub const CALIBRATION_ID_LENGTH: usize = 16;
+
+/// Strip the padding from a fixed width ASCII field.
+fn trim_padding(field: &[u8]) -> &[u8] {
+    let length = field
+        .iter()
+        .rposition(|&byte| byte != 0x00 && byte != PDU_NOT_AVAILABLE)
+        .map_or(0, |position| position + 1);
+
+    &field[..length]
+}
+
+/// Calibration information (DM19).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Calibration<'a> {
+    /// Calibration verification number (CVN).
+    pub verification_number: u32,
+    /// Calibration identification (CAL ID) in ASCII, without padding.
+    pub identification: &'a [u8],
+}
+
+impl<'a> Calibration<'a> {
+    /// Returns `None` if the PDU is shorter than a calibration record.
+    pub fn from_pdu(pdu: &'a [u8]) -> Option<Self> {
+        let pdu = pdu.get(..CALIBRATION_LENGTH)?;
+
+        Some(Self {
+            verification_number: u32::from_le_bytes([pdu[0], pdu[1], pdu[2], pdu[3]]),
+            identification: trim_padding(&pdu[4..]),
+        })
+    }
+
+    /// Encode the calibration record, the identification is padded with zeros.
+    ///
+    /// Returns `None` if the identification is too long.
+    pub fn to_pdu(&self) -> Option<[u8; CALIBRATION_LENGTH]> {
+        let mut pdu = [0x00; CALIBRATION_LENGTH];
+
+        pdu[..4].copy_from_slice(&self.verification_number.to_le_bytes());
+        pdu.get_mut(4..4 + self.identification.len())?
+            .copy_from_slice(self.identification);
+
+        Some(pdu)
+    }
+}
+
+impl core::fmt::Display for Calibration<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "CVN: 0x{:08X}, CAL ID: ", self.verification_number)?;
+        for &byte in self.identification {
+            write!(f, "{}", byte as char)?;
+        }
+        Ok(())
+    }
+}
+
+/// Iterator over the calibration records in a DM19 message.
+///
+/// A trailing partial record is ignored.
+pub struct Calibrations<'a> {
+    pdu: &'a [u8],
+}
+
+impl<'a> Calibrations<'a> {
+    /// Decode the calibration records from a single frame or a reassembled transport payload.
+    pub fn from_pdu(pdu: &'a [u8]) -> Self {
+        Self { pdu }
+    }
+}
+
+impl<'a> Iterator for Calibrations<'a> {
+    type Item = Calibration<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let calibration = Calibration::from_pdu(self.pdu)?;
+
+        self.pdu = &self.pdu[CALIBRATION_LENGTH..];
+
+        Some(calibration)
+    }
+}
+
+/// Number of bytes in a monitor performance ratio record.
+pub const MONITOR_RATIO_LENGTH: usize = 7;
+
+/// In-use performance ratio of a monitor.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MonitorRatio {
+    /// SPN identifying the monitor.
+    pub suspect_parameter_number: u32,
+    /// Number of times the monitor ran.
+    pub numerator: Option<u16>,
+    /// Number of times the vehicle was operated in the conditions of the monitor.
+    pub denominator: Option<u16>,
+}
+
+impl MonitorRatio {
+    /// Returns `None` if the PDU is shorter than 7 bytes.
+    pub fn from_pdu(pdu: &[u8]) -> Option<Self> {
+        if pdu.len() < MONITOR_RATIO_LENGTH {
+            return None;
+        }
+
+        Some(Self {
+            suspect_parameter_number: SpnConversionMethod::Version4.decode(pdu),
+            numerator: slots::count2::dec([pdu[3], pdu[4]]),
+            denominator: slots::count2::dec([pdu[5], pdu[6]]),
+        })
+    }
+
+    pub fn to_pdu(&self) -> [u8; MONITOR_RATIO_LENGTH] {
+        let spn = SpnConversionMethod::Version4.encode(self.suspect_parameter_number);
+        let numerator = slots::count2::enc(self.numerator);
+        let denominator = slots::count2::enc(self.denominator);
+
+        [
+            spn[0],
+            spn[1],
+            spn[2] | 0x1F,
+            numerator[0],
+            numerator[1],
+            denominator[0],
+            denominator[1],
+        ]
+    }
+}
+
+impl core::fmt::Display for MonitorRatio {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "SPN: {}, Numerator: ", self.suspect_parameter_number)?;
+        fmt_count(f, self.numerator)?;
+        write!(f, ", Denominator: ")?;
+        fmt_count(f, self.denominator)
+    }
+}
+
+/// Monitor performance ratio (DM20).
+///
+/// The general counters are followed by the ratio of every monitor.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MonitorPerformanceRatio<'a> {
+    /// Number of ignition cycles.
+    pub ignition_cycles: Option<u16>,
+    /// Number of times the general monitoring conditions were encountered.
+    pub monitoring_conditions_encountered: Option<u16>,
+    ratios: &'a [u8],
+}
+
+impl<'a> MonitorPerformanceRatio<'a> {
+    /// Decode the message from a single frame or a reassembled transport payload.
+    ///
+    /// Returns `None` if the PDU is shorter than the general counters.
+    pub fn from_pdu(pdu: &'a [u8]) -> Option<Self> {
+        if pdu.len() < 4 {
+            return None;
+        }
+
+        Some(Self {
+            ignition_cycles: slots::count2::dec([pdu[0], pdu[1]]),
+            monitoring_conditions_encountered: slots::count2::dec([pdu[2], pdu[3]]),
+            ratios: &pdu[4..],
+        })
+    }
+
+    /// Returns an iterator over the monitor ratios.
+    ///
+    /// A trailing partial record is ignored.
+    pub fn ratios(&self) -> impl Iterator<Item = MonitorRatio> + 'a {
+        self.ratios
+            .chunks_exact(MONITOR_RATIO_LENGTH)
+            .filter_map(MonitorRatio::from_pdu)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::AbortReason;
+
+    #[test]
+    fn diagnostic_1_message_1() {
+        let diagnostic_message = Message1::from_pdu(&[0x57, 0xFF, 0x9F, 0x00, 0x03, 0x01]);
+
+        assert_eq!(diagnostic_message.protect_lamp, None);
+        assert_eq!(diagnostic_message.amber_warning_lamp, Some(LampStatus::On));
+        assert_eq!(diagnostic_message.red_stop_lamp, Some(LampStatus::On));
+        assert_eq!(
+            diagnostic_message.malfunction_indicator_lamp,
+            Some(LampStatus::On)
+        );
+        assert_eq!(diagnostic_message.protect_lamp_flash, None);
+        assert_eq!(diagnostic_message.amber_warning_lamp_flash, None);
+        assert_eq!(diagnostic_message.red_stop_lamp_flash, None);
+        assert_eq!(diagnostic_message.malfunction_indicator_lamp_flash, None);
+        assert_eq!(diagnostic_message.suspect_parameter_number, 159);
+        assert_eq!(
+            diagnostic_message.failure_mode_identifier,
+            Fmi::VoltageAboveNormal
+        );
+        assert_eq!(diagnostic_message.spn_conversion_method, 0);
+        assert_eq!(diagnostic_message.occurrence_count, 1);
+    }
+
+    #[test]
+    fn diagnostic_1_message_2() {
+        let diagnostic_message = Message1::from_pdu(&[0x57, 0xFF, 0xFB, 0x06, 0x0B, 0x32]);
+
+        assert_eq!(diagnostic_message.protect_lamp, None);
+        assert_eq!(diagnostic_message.amber_warning_lamp, Some(LampStatus::On));
+        assert_eq!(diagnostic_message.red_stop_lamp, Some(LampStatus::On));
+        assert_eq!(
+            diagnostic_message.malfunction_indicator_lamp,
+            Some(LampStatus::On)
+        );
+        assert_eq!(diagnostic_message.protect_lamp_flash, None);
+        assert_eq!(diagnostic_message.amber_warning_lamp_flash, None);
+        assert_eq!(diagnostic_message.red_stop_lamp_flash, None);
+        assert_eq!(diagnostic_message.malfunction_indicator_lamp_flash, None);
+        assert_eq!(diagnostic_message.suspect_parameter_number, 1787);
+        assert_eq!(
+            diagnostic_message.failure_mode_identifier,
+            Fmi::RootCauseNotKnown
+        );
+        assert_eq!(diagnostic_message.spn_conversion_method, 0);
+        assert_eq!(diagnostic_message.occurrence_count, 50);
+    }
+
+    #[test]
+    fn diagnostic_1_message_3() {
+        let diagnostic_message = Message1::from_pdu(&[0x40, 0xFF, 0x7F, 0x02, 0x02, 0x00]);
+
+        assert_eq!(diagnostic_message.protect_lamp, Some(LampStatus::Off));
+        assert_eq!(diagnostic_message.amber_warning_lamp, Some(LampStatus::Off));
+        assert_eq!(diagnostic_message.red_stop_lamp, Some(LampStatus::Off));
+        assert_eq!(
+            diagnostic_message.malfunction_indicator_lamp,
+            Some(LampStatus::On)
+        );
+        assert_eq!(diagnostic_message.protect_lamp_flash, None);
+        assert_eq!(diagnostic_message.amber_warning_lamp_flash, None);
+        assert_eq!(diagnostic_message.red_stop_lamp_flash, None);
+        assert_eq!(diagnostic_message.malfunction_indicator_lamp_flash, None);
+        assert_eq!(diagnostic_message.suspect_parameter_number, 639);
+        assert_eq!(diagnostic_message.failure_mode_identifier, Fmi::DataErratic);
+        assert_eq!(diagnostic_message.spn_conversion_method, 0);
+        assert_eq!(diagnostic_message.occurrence_count, 0);
+    }
+
+    #[test]
+    fn diagnostic_1_message_4() {
+        let diagnostic_message = Message1::from_pdu(&[0x00, 0xFF, 0x00, 0x00, 0x00, 0x00]);
+
+        assert_eq!(diagnostic_message.protect_lamp, Some(LampStatus::Off));
+        assert_eq!(diagnostic_message.amber_warning_lamp, Some(LampStatus::Off));
+        assert_eq!(diagnostic_message.red_stop_lamp, Some(LampStatus::Off));
+        assert_eq!(
+            diagnostic_message.malfunction_indicator_lamp,
+            Some(LampStatus::Off)
+        );
+        assert_eq!(diagnostic_message.protect_lamp_flash, None);
+        assert_eq!(diagnostic_message.amber_warning_lamp_flash, None);
+        assert_eq!(diagnostic_message.red_stop_lamp_flash, None);
+        assert_eq!(diagnostic_message.malfunction_indicator_lamp_flash, None);
+        assert_eq!(diagnostic_message.suspect_parameter_number, 0);
+        assert_eq!(
+            diagnostic_message.failure_mode_identifier,
+            Fmi::AboveNormalMostSevere
+        );
+        assert_eq!(diagnostic_message.spn_conversion_method, 0);
+        assert_eq!(diagnostic_message.occurrence_count, 0);
+    }
+
+    #[test]
+    fn diagnostic_1_message_5() {
+        let diagnostic_message_encoded = Message1 {
+            protect_lamp: Some(LampStatus::Off),
+            amber_warning_lamp: Some(LampStatus::Off),
+            red_stop_lamp: Some(LampStatus::Off),
+            malfunction_indicator_lamp: Some(LampStatus::On),
+            protect_lamp_flash: None,
+            amber_warning_lamp_flash: None,
+            red_stop_lamp_flash: None,
+            malfunction_indicator_lamp_flash: None,
+            suspect_parameter_number: 639,
+            failure_mode_identifier: Fmi::DataErratic,
+            spn_conversion_method: 0,
+            occurrence_count: 0,
+        }
+        .to_pdu();
+
+        assert_eq!(
+            diagnostic_message_encoded,
+            [0x40, 0xFF, 0x7F, 0x02, 0x02, 0x00, 0xFF, 0xFF]
+        );
+    }
+
+    #[test]
+    fn diagnostic_1_message_6() {
+        let diagnostic_message_encoded = Message1 {
+            protect_lamp: Some(LampStatus::On),
+            amber_warning_lamp: Some(LampStatus::Off),
+            red_stop_lamp: Some(LampStatus::On),
+            malfunction_indicator_lamp: Some(LampStatus::On),
+            protect_lamp_flash: Some(FlashStatus::Slow),
+            amber_warning_lamp_flash: None,
+            red_stop_lamp_flash: Some(FlashStatus::Slow),
+            malfunction_indicator_lamp_flash: Some(FlashStatus::Fast),
+            suspect_parameter_number: 639,
+            failure_mode_identifier: Fmi::DataErratic,
+            spn_conversion_method: 0,
+            occurrence_count: 0,
+        }
+        .to_pdu();
+
+        let diagnostic_message_decoded = Message1::from_pdu(&diagnostic_message_encoded);
+
+        assert_eq!(
+            diagnostic_message_decoded.protect_lamp,
+            Some(LampStatus::On)
+        );
+        assert_eq!(
+            diagnostic_message_decoded.amber_warning_lamp,
+            Some(LampStatus::Off)
+        );
+        assert_eq!(
+            diagnostic_message_decoded.red_stop_lamp,
+            Some(LampStatus::On)
+        );
+        assert_eq!(
+            diagnostic_message_decoded.malfunction_indicator_lamp,
+            Some(LampStatus::On)
+        );
+
+        assert_eq!(
+            diagnostic_message_decoded.protect_lamp_flash,
+            Some(FlashStatus::Slow)
+        );
+        assert_eq!(diagnostic_message_decoded.amber_warning_lamp_flash, None);
+        assert_eq!(
+            diagnostic_message_decoded.red_stop_lamp_flash,
+            Some(FlashStatus::Slow)
+        );
+        assert_eq!(
+            diagnostic_message_decoded.malfunction_indicator_lamp_flash,
+            Some(FlashStatus::Fast)
+        );
+    }
+
+    #[test]
+    fn diagnostic_trouble_code_1() {
+        let dtc = Dtc::from_pdu(&[0x0F, 0x1B, 0xE3, 0x85]);
+
+        assert_eq!(dtc.suspect_parameter_number, 0x71B0F);
+        assert_eq!(dtc.failure_mode_identifier, Fmi::VoltageAboveNormal);
+        assert_eq!(dtc.spn_conversion_method, 1);
+        assert_eq!(dtc.occurrence_count, 5);
+        assert_eq!(dtc.to_pdu(), [0x0F, 0x1B, 0xE3, 0x85]);
+    }
+
+    #[test]
+    fn diagnostic_message_single_frame() {
+        let pdu = [0x04, 0xFF, 0x9F, 0x00, 0x03, 0x01, 0xFF, 0xFF];
+
+        let message = DiagnosticMessage::<4>::from_pdu(&pdu).unwrap();
+
+        assert_eq!(message.lamps.amber_warning_lamp, Some(LampStatus::On));
+        assert_eq!(message.len(), 1);
+        assert!(!message.is_multi_packet());
+        assert_eq!(message.dtc()[0].suspect_parameter_number, 159);
+        assert_eq!(
+            message.dtc()[0].failure_mode_identifier,
+            Fmi::VoltageAboveNormal
+        );
+        assert_eq!(message.dtc()[0].occurrence_count, 1);
+        assert_eq!(message.to_pdu(), pdu);
+
+        let mut buffer = [0; 16];
+        assert_eq!(message.copy_to_slice(&mut buffer), Some(8));
+        assert_eq!(&buffer[..8], &pdu);
+    }
+
+    #[test]
+    fn diagnostic_message_no_dtc() {
+        let message = DiagnosticMessage::<4>::new(Lamps::default());
+
+        let pdu = message.to_pdu();
+        assert_eq!(pdu, [0xFF, 0xFF, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF]);
+        assert!(DiagnosticMessage::<4>::from_pdu(&pdu).unwrap().is_empty());
+        assert!(DiagnosticMessage::<4>::from_pdu(&pdu[..1]).is_none());
+    }
+
+    #[test]
+    fn diagnostic_message_multi_packet() {
+        let mut message = DiagnosticMessage::<2>::new(Lamps {
+            amber_warning_lamp: Some(LampStatus::On),
+            ..Default::default()
+        });
+        message
+            .push(Dtc {
+                suspect_parameter_number: 0x7FFFF,
+                failure_mode_identifier: Fmi::ConditionExists,
+                spn_conversion_method: 0,
+                occurrence_count: 126,
+            })
+            .unwrap();
+        message
+            .push(Dtc {
+                suspect_parameter_number: 190,
+                failure_mode_identifier: Fmi::DataErratic,
+                spn_conversion_method: 0,
+                occurrence_count: 1,
+            })
+            .unwrap();
+        assert!(message
+            .push(Dtc {
+                suspect_parameter_number: 1,
+                failure_mode_identifier: Fmi::BelowNormalMostSevere,
+                spn_conversion_method: 0,
+                occurrence_count: 1,
+            })
+            .is_err());
+
+        assert!(message.is_multi_packet());
+
+        let mut buffer = [0; 16];
+        assert_eq!(message.copy_to_slice(&mut buffer[..8]), None);
+        assert_eq!(message.copy_to_slice(&mut buffer), Some(10));
+        assert_eq!(
+            &buffer[..10],
+            &[0xF7, 0xFF, 0xFF, 0xFF, 0xFF, 0x7E, 0xBE, 0x00, 0x02, 0x01]
+        );
+
+        let decoded = DiagnosticMessage::<2>::from_pdu(&buffer[..10]).unwrap();
+        assert_eq!(decoded, message);
+    }
+
+    #[test]
+    fn diagnostic_1_message_7() {
+        let pdu = [0x04, 0xFF, 0x0F, 0x1B, 0xE3, 0x05, 0xFF, 0xFF];
+
+        let diagnostic_message = Message1::from_pdu(&pdu);
+
+        assert_eq!(diagnostic_message.suspect_parameter_number, 0x71B0F);
+        assert_eq!(
+            diagnostic_message.failure_mode_identifier,
+            Fmi::VoltageAboveNormal
+        );
+        assert_eq!(diagnostic_message.to_pdu(), pdu);
+    }
+
+    #[test]
+    fn diagnostic_trouble_code_conversion_method() {
+        let dtc = Dtc {
+            suspect_parameter_number: 0x5A3C7,
+            failure_mode_identifier: Fmi::VoltageBelowNormal,
+            spn_conversion_method: 0,
+            occurrence_count: 2,
+        };
+
+        let pdu = dtc.to_pdu_with_method(SpnConversionMethod::Version1);
+        assert_eq!(pdu, [0xB4, 0x78, 0xE4, 0x82]);
+        assert_eq!(
+            Dtc::from_pdu_with_method(&pdu, SpnConversionMethod::Version1).suspect_parameter_number,
+            0x5A3C7
+        );
+
+        let pdu = dtc.to_pdu_with_method(SpnConversionMethod::Version2);
+        assert_eq!(pdu, [0xA3, 0xC7, 0xA4, 0x82]);
+        assert_eq!(
+            Dtc::from_pdu_with_method(&pdu, SpnConversionMethod::Version2).suspect_parameter_number,
+            0x5A3C7
+        );
+
+        let pdu = dtc.to_pdu_with_method(SpnConversionMethod::Version3);
+        assert_eq!(pdu, [0xC7, 0xA3, 0xA4, 0x82]);
+        assert_eq!(Dtc::from_pdu(&pdu).suspect_parameter_number, 0x5A3C7);
+
+        let pdu = dtc.to_pdu_with_method(SpnConversionMethod::Version4);
+        assert_eq!(pdu, dtc.to_pdu());
+        assert_eq!(
+            Dtc::from_pdu_with_method(&pdu, SpnConversionMethod::Version1),
+            dtc
+        );
+    }
+
+    #[test]
+    fn failure_mode_identifier_1() {
+        for value in 0..32 {
+            assert_eq!(u8::from(Fmi::from(value)), value);
+        }
+
+        assert_eq!(Fmi::from(9), Fmi::AbnormalUpdateRate);
+        assert_eq!(Fmi::from(25), Fmi::Reserved(25));
+        assert_eq!(Fmi::from(0x3F), Fmi::ConditionExists);
+    }
+
+    #[test]
+    fn failure_mode_identifier_2() {
+        assert_eq!(Fmi::from(0).severity(), Some(FmiSeverity::MostSevere));
+        assert_eq!(
+            Fmi::from(16).severity(),
+            Some(FmiSeverity::ModeratelySevere)
+        );
+        assert_eq!(Fmi::from(17).severity(), Some(FmiSeverity::LeastSevere));
+        assert_eq!(Fmi::VoltageBelowNormal.severity(), None);
+
+        assert_eq!(Fmi::from(15).category(), FmiCategory::Range);
+        assert_eq!(Fmi::from(5).category(), FmiCategory::Electrical);
+        assert_eq!(Fmi::from(9).category(), FmiCategory::Signal);
+        assert_eq!(Fmi::from(19).category(), FmiCategory::Network);
+        assert_eq!(Fmi::from(13).category(), FmiCategory::Component);
+        assert_eq!(Fmi::from(31).category(), FmiCategory::Other);
+    }
+
+    #[test]
+    fn diagnostic_message_2() {
+        let frame = request_previously_active(0x00, 0xF9);
+        assert_eq!(frame.id().as_raw(), 0x18EA00F9);
+        assert_eq!(frame.pdu(), &[0xCB, 0xFE, 0x00]);
+
+        let pdu = [
+            0x00, 0xFF, 0x9F, 0x00, 0x03, 0x01, 0xBE, 0x00, 0x02, 0x02, 0x6E, 0x00, 0x0B, 0x7F,
+        ];
+
+        let message = PreviouslyActiveDiagnosticTroubleCodes::<4>::from_pdu(&pdu).unwrap();
+        assert_eq!(message.len(), 3);
+        assert_eq!(message.lamps.red_stop_lamp, Some(LampStatus::Off));
+        assert_eq!(message.dtc()[1].suspect_parameter_number, 190);
+        assert_eq!(
+            message.dtc()[2].failure_mode_identifier,
+            Fmi::RootCauseNotKnown
+        );
+        assert_eq!(message.dtc()[2].occurrence_count, 127);
+
+        let mut buffer = [0; 14];
+        assert_eq!(message.copy_to_slice(&mut buffer), Some(14));
+        assert_eq!(buffer, pdu);
+    }
+
+    #[test]
+    fn diagnostic_message_3() {
+        let frame = clear_previously_active(0x00, 0xF9);
+        assert_eq!(frame.id().as_raw(), 0x18EA00F9);
+        assert_eq!(frame.pdu(), &[0xCC, 0xFE, 0x00]);
+
+        let id = crate::IdBuilder::from_pgn(PGN::AcknowledgmentMessage)
+            .sa(0x00)
+            .da(0xF9)
+            .build();
+
+        let frame = Frame::new(id, [0x00, 0xFF, 0xFF, 0xFF, 0xF9, 0xCC, 0xFE, 0x00]);
+        assert_eq!(
+            clear_response(&frame, PGN::DiagnosticMessage3),
+            Some(AcknowledgementControl::Acknowledged)
+        );
+        assert_eq!(clear_response(&frame, PGN::DiagnosticMessage11), None);
+
+        let frame = Frame::new(id, [0x02, 0xFF, 0xFF, 0xFF, 0xF9, 0xCC, 0xFE, 0x00]);
+        assert_eq!(
+            clear_response(&frame, PGN::DiagnosticMessage3),
+            Some(AcknowledgementControl::AccessDenied)
+        );
+    }
+
+    #[test]
+    fn diagnostic_message_4() {
+        let pdu = [
+            0x0E, 0x6E, 0x00, 0x00, 0x01, 0xF0, 0x96, 0x80, 0x3E, 0x40, 0xE6, 0x00, 0x32, 0xAA,
+            0xBB, 0x0C, 0x9F, 0x00, 0x03, 0x02, 0xF1, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+        ];
+
+        let mut freeze_frames = FreezeFrames::from_pdu(&pdu);
+
+        let freeze_frame = freeze_frames.next().unwrap();
+        assert_eq!(freeze_frame.dtc.suspect_parameter_number, 110);
+        assert_eq!(
+            freeze_frame.dtc.failure_mode_identifier,
+            Fmi::AboveNormalMostSevere
+        );
+        assert_eq!(freeze_frame.dtc.occurrence_count, 1);
+        assert_eq!(
+            freeze_frame.engine_torque_mode,
+            Some(EngineTorqueMode::NoRequest)
+        );
+        assert_eq!(freeze_frame.boost_pressure, Some(300));
+        assert_eq!(freeze_frame.engine_speed, Some(2000));
+        assert_eq!(freeze_frame.engine_load, Some(64));
+        assert_eq!(freeze_frame.engine_coolant_temperature, Some(190));
+        assert_eq!(freeze_frame.vehicle_speed, Some(50));
+        assert_eq!(freeze_frame.manufacturer_data, &[0xAA, 0xBB]);
+
+        let mut buffer = [0; 15];
+        assert_eq!(freeze_frame.copy_to_slice(&mut buffer), Some(15));
+        assert_eq!(buffer, pdu[..15]);
+
+        let freeze_frame = freeze_frames.next().unwrap();
+        assert_eq!(freeze_frame.dtc.suspect_parameter_number, 159);
+        assert_eq!(
+            freeze_frame.engine_torque_mode,
+            Some(EngineTorqueMode::AcceleratorPedal)
+        );
+        assert_eq!(freeze_frame.boost_pressure, None);
+        assert_eq!(freeze_frame.engine_speed, None);
+        assert_eq!(freeze_frame.vehicle_speed, None);
+        assert!(freeze_frame.manufacturer_data.is_empty());
+
+        assert!(freeze_frames.next().is_none());
+    }
+
+    #[test]
+    fn diagnostic_message_4_truncated() {
+        let pdu = [0x10, 0x6E, 0x00, 0x00, 0x01, 0xF0, 0x32, 0x80, 0x3E];
+        assert!(FreezeFrames::from_pdu(&pdu).next().is_none());
+
+        let pdu = [0x04, 0x6E, 0x00, 0x00, 0x01];
+        assert!(FreezeFrames::from_pdu(&pdu).next().is_none());
+    }
+
+    #[test]
+    fn diagnostic_message_5() {
+        let pdu = [0x02, 0x05, 0x14, 0x57, 0x21, 0xE4, 0x01, 0xE0];
+
+        let message = DiagnosticReadiness1::from_pdu(&pdu).unwrap();
+
+        assert_eq!(message.active_trouble_codes, Some(2));
+        assert_eq!(message.previously_active_trouble_codes, Some(5));
+        assert_eq!(message.obd_compliance, Some(ObdCompliance::HdObd));
+        assert_eq!(
+            message.continuously_monitored_systems_support,
+            ContinuouslyMonitoredSystems {
+                misfire: true,
+                fuel_system: true,
+                comprehensive_component: true,
+            }
+        );
+        assert_eq!(
+            message.continuously_monitored_systems_status,
+            ContinuouslyMonitoredSystems {
+                misfire: true,
+                fuel_system: false,
+                comprehensive_component: true,
+            }
+        );
+        assert!(message.non_continuously_monitored_systems_support.catalyst);
+        assert!(
+            message
+                .non_continuously_monitored_systems_support
+                .exhaust_gas_sensor
+        );
+        assert!(
+            message
+                .non_continuously_monitored_systems_support
+                .diesel_particulate_filter
+        );
+        assert!(
+            !message
+                .non_continuously_monitored_systems_support
+                .egr_system
+        );
+        assert!(message.non_continuously_monitored_systems_status.catalyst);
+        assert!(
+            !message
+                .non_continuously_monitored_systems_status
+                .diesel_particulate_filter
+        );
+
+        assert_eq!(
+            message.to_pdu(),
+            [0x02, 0x05, 0x14, 0xDF, 0x21, 0xE4, 0x01, 0xE0]
+        );
+    }
+
+    #[test]
+    fn diagnostic_message_5_not_available() {
+        let message =
+            DiagnosticReadiness1::from_pdu(&[0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00, 0x00, 0x00])
+                .unwrap();
+
+        assert_eq!(message.active_trouble_codes, None);
+        assert_eq!(message.previously_active_trouble_codes, None);
+        assert_eq!(message.obd_compliance, None);
+        assert_eq!(
+            ObdCompliance::from_value(0x40),
+            Some(ObdCompliance::Other(0x40))
+        );
+        assert_eq!(
+            ObdCompliance::to_value(Some(ObdCompliance::Other(0x40))),
+            0x40
+        );
+    }
+
+    #[test]
+    fn diagnostic_message_5_short() {
+        assert!(DiagnosticReadiness1::from_pdu(&[0x02, 0x05, 0x14, 0x57]).is_none());
+    }
+
+    #[test]
+    fn diagnostic_message_11() {
+        let frame = clear_active(0x00, 0xF9);
+        assert_eq!(frame.id().as_raw(), 0x18EA00F9);
+        assert_eq!(frame.pdu(), &[0xD3, 0xFE, 0x00]);
+        assert_eq!(
+            protocol::request_from_pdu(frame.pdu()),
+            PGN::DiagnosticMessage11
+        );
+
+        let frame = acknowledge_clear(0x00, PGN::DiagnosticMessage11);
+        assert_eq!(frame.id().as_raw(), 0x18E8FF00);
+        assert_eq!(
+            clear_response(&frame, PGN::DiagnosticMessage11),
+            Some(AcknowledgementControl::Acknowledged)
+        );
+        assert_eq!(clear_response(&frame, PGN::DiagnosticMessage3), None);
+    }
+
+    #[test]
+    fn diagnostic_message_12() {
+        let frame = request_emission_related_active(0x00, 0xF9);
+        assert_eq!(frame.pdu(), &[0xD4, 0xFE, 0x00]);
+
+        let pdu = [0x40, 0xFF, 0x66, 0x0D, 0x12, 0x03, 0xFF, 0xFF];
+
+        let message = EmissionRelatedActiveDiagnosticTroubleCodes::<2>::from_pdu(&pdu).unwrap();
+        assert_eq!(
+            message.lamps.malfunction_indicator_lamp,
+            Some(LampStatus::On)
+        );
+        assert_eq!(message.len(), 1);
+        assert_eq!(message.dtc()[0].suspect_parameter_number, 3430);
+        assert_eq!(
+            message.dtc()[0].failure_mode_identifier,
+            Fmi::BelowNormalModeratelySevere
+        );
+        assert_eq!(message.to_pdu(), pdu);
+    }
+
+    #[test]
+    fn diagnostic_message_13() {
+        let pdu = [0x3C, 0xFF, 0xFF, 0x02, 0x3C, 0x00, 0xFF, 0xFF];
+
+        let message = StopStartBroadcast::from_pdu(&pdu).unwrap();
+
+        assert_eq!(message.current_data_link, Some(BroadcastCommand::Stop));
+        assert_eq!(message.j1587, None);
+        assert_eq!(message.j1939_network1, Some(BroadcastCommand::Stop));
+        assert_eq!(message.j1939_network2, None);
+        assert_eq!(message.hold_signal, Some(HoldSignal::AllDevices));
+        assert_eq!(message.suspend_signal, Some(SuspendSignal::TemporaryAll));
+        assert_eq!(message.suspend_duration, Some(60));
+        assert_eq!(message.to_pdu(), pdu);
+    }
+
+    #[test]
+    fn diagnostic_message_13_short() {
+        assert!(StopStartBroadcast::from_pdu(&[0x3C, 0xFF, 0xFF, 0x02, 0x3C]).is_none());
+    }
+
+    #[test]
+    fn broadcast_suspension_hold() {
+        let stop = StopStartBroadcast::from_pdu(&[0xFC, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF])
+            .unwrap();
+        let hold = StopStartBroadcast::from_pdu(&[0xFF, 0xFF, 0xFF, 0x0F, 0xFF, 0xFF, 0xFF, 0xFF])
+            .unwrap();
+        let start = StopStartBroadcast::from_pdu(&[0xFD, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF])
+            .unwrap();
+
+        let mut suspension = BroadcastSuspension::new();
+        suspension.from_message(&hold);
+        assert!(!suspension.tick(Duration::from_secs(0)));
+
+        suspension.from_message(&stop);
+        assert!(suspension.tick(Duration::from_secs(1)));
+        assert!(suspension.tick(Duration::from_secs(5)));
+
+        suspension.from_message(&hold);
+        assert!(suspension.tick(Duration::from_secs(6)));
+        assert!(suspension.tick(Duration::from_secs(11)));
+        assert!(!suspension.tick(Duration::from_secs(12)));
+
+        suspension.from_message(&stop);
+        assert!(suspension.tick(Duration::from_secs(13)));
+        suspension.from_message(&start);
+        assert!(!suspension.tick(Duration::from_secs(14)));
+    }
+
+    #[test]
+    fn broadcast_suspension_duration() {
+        let stop = StopStartBroadcast::from_pdu(&[0xFC, 0xFF, 0xFF, 0x02, 0x03, 0x00, 0xFF, 0xFF])
+            .unwrap();
+
+        let mut suspension = BroadcastSuspension::new();
+        suspension.from_message(&stop);
+        assert!(suspension.tick(Duration::from_secs(0)));
+        assert!(suspension.tick(Duration::from_secs(2)));
+        assert!(!suspension.tick(Duration::from_secs(3)));
+    }
+
+    #[test]
+    fn memory_access_messages() {
+        let request =
+            MemoryAccessRequest::from_pdu(&[0x14, 0x23, 0x00, 0x10, 0x02, 0x00, 0xFF, 0xFF])
+                .unwrap();
+
+        assert_eq!(request.length, 0x114);
+        assert_eq!(request.pointer_type, PointerType::Direct);
+        assert_eq!(request.command, MemoryAccessCommand::Read);
+        assert_eq!(request.pointer, 0x21000);
+        assert_eq!(request.pointer_extension, 0);
+        assert_eq!(request.key, None);
+        assert_eq!(
+            request.to_pdu(),
+            [0x14, 0x23, 0x00, 0x10, 0x02, 0x00, 0xFF, 0xFF]
+        );
+
+        let response =
+            MemoryAccessResponse::from_pdu(&[0x08, 0x1B, 0x01, 0x00, 0x00, 0xFF, 0x34, 0x12])
+                .unwrap();
+
+        assert_eq!(response.length, 8);
+        assert_eq!(response.status, Some(MemoryAccessStatus::OperationFailed));
+        assert_eq!(response.edcp, Some(1));
+        assert_eq!(response.edcp_extension, None);
+        assert_eq!(response.seed, Some(0x1234));
+        assert_eq!(
+            response.to_pdu(),
+            [0x08, 0x1B, 0x01, 0x00, 0x00, 0xFF, 0x34, 0x12]
+        );
+
+        let mut pdu = [0; 8];
+        let message = BinaryDataTransfer {
+            data: &[0xDE, 0xAD, 0xBE, 0xEF],
+        };
+
+        assert_eq!(message.copy_to_slice(&mut pdu), Some(5));
+        assert_eq!(BinaryDataTransfer::from_pdu(&pdu[..5]), Some(message));
+        assert_eq!(BinaryDataTransfer::from_pdu(&pdu[..4]), None);
+
+        assert_eq!(
+            MemoryAccessRequest::from_pdu(&[0x14, 0x23, 0x00, 0x10]),
+            None
+        );
+        assert_eq!(
+            MemoryAccessResponse::from_pdu(&[0x08, 0x1B, 0x01, 0x00]),
+            None
+        );
+    }
+
+    fn memory_access_response(status: MemoryAccessStatus, seed: Option<u16>) -> Frame {
+        let response = MemoryAccessResponse {
+            length: 0,
+            status: Some(status),
+            edcp: None,
+            edcp_extension: None,
+            seed,
+        };
+
+        FrameBuilder::new(
+            IdBuilder::from_pgn(PGN::DiagnosticMessage15)
+                .sa(0x00)
+                .da(0xF9)
+                .build(),
+        )
+        .copy_from_slice(&response.to_pdu())
+        .build()
+    }
+
+    #[test]
+    fn memory_access_read() {
+        let mut data = [0; 20];
+        for (i, byte) in data.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+
+        let mut pdu = [0; 21];
+        BinaryDataTransfer { data: &data }.copy_to_slice(&mut pdu);
+
+        let mut client = MemoryAccessClient::new(
+            0xF9,
+            0x00,
+            MemoryAccessRequest {
+                length: 20,
+                pointer_type: PointerType::Direct,
+                command: MemoryAccessCommand::Read,
+                pointer: 0x1000,
+                pointer_extension: 0,
+                key: None,
+            },
+        );
+
+        let frame = client.tick(Duration::from_millis(0)).unwrap().unwrap();
+        assert_eq!(frame.id().pgn(), PGN::DiagnosticMessage14);
+        assert_eq!(frame.id().destination_address(), Some(0x00));
+        assert_eq!(
+            frame.pdu(),
+            &[0x14, 0x03, 0x00, 0x10, 0x00, 0x00, 0xFF, 0xFF]
+        );
+
+        client
+            .from_frame(&memory_access_response(MemoryAccessStatus::Proceed, None))
+            .unwrap();
+        assert_eq!(client.state(), MemoryAccessClientState::DataTransfer);
+
+        let mut ecu = ConnectionTransmitter::new(0x00, 0xF9, PGN::DiagnosticMessage16)
+            .with_data(&pdu)
+            .unwrap();
+
+        for millis in 0..10 {
+            let now = Duration::from_millis(millis);
+
+            if let Some(frame) = ecu.tick(now) {
+                client.from_frame(&frame).unwrap();
+            }
+            if let Some(frame) = client.tick(now).unwrap() {
+                ecu.from_frame(&frame).unwrap();
+            }
+        }
+
+        assert!(ecu.is_complete());
+        assert_eq!(client.state(), MemoryAccessClientState::Operation);
+        assert_eq!(client.data(), &data);
+
+        client
+            .from_frame(&memory_access_response(
+                MemoryAccessStatus::OperationCompleted,
+                None,
+            ))
+            .unwrap();
+
+        let frame = client.tick(Duration::from_millis(10)).unwrap().unwrap();
+        assert_eq!(
+            frame.pdu(),
+            &[0x14, 0x09, 0x00, 0x10, 0x00, 0x00, 0xFF, 0xFF]
+        );
+        assert!(client.is_complete());
+    }
+
+    #[test]
+    fn memory_access_write_with_key() {
+        let mut pdu = [0; 5];
+        BinaryDataTransfer {
+            data: &[0xDE, 0xAD, 0xBE, 0xEF],
+        }
+        .copy_to_slice(&mut pdu);
+
+        let mut client = MemoryAccessClient::new(
+            0xF9,
+            0x00,
+            MemoryAccessRequest {
+                length: 4,
+                pointer_type: PointerType::Direct,
+                command: MemoryAccessCommand::Write,
+                pointer: 0x1000,
+                pointer_extension: 0,
+                key: None,
+            },
+        )
+        .with_data(&pdu);
+
+        client.tick(Duration::from_millis(0)).unwrap().unwrap();
+        client
+            .from_frame(&memory_access_response(
+                MemoryAccessStatus::Proceed,
+                Some(0x1234),
+            ))
+            .unwrap();
+        assert_eq!(client.state(), MemoryAccessClientState::Seed(0x1234));
+        assert!(client.tick(Duration::from_millis(5000)).unwrap().is_none());
+
+        client.key(0x4321);
+
+        let frame = client.tick(Duration::from_millis(5000)).unwrap().unwrap();
+        assert_eq!(
+            frame.pdu(),
+            &[0x04, 0x05, 0x00, 0x10, 0x00, 0x00, 0x21, 0x43]
+        );
+
+        client
+            .from_frame(&memory_access_response(MemoryAccessStatus::Proceed, None))
+            .unwrap();
+
+        let frame = client.tick(Duration::from_millis(5010)).unwrap().unwrap();
+        assert_eq!(frame.id().pgn(), PGN::DiagnosticMessage16);
+        assert_eq!(
+            frame.pdu(),
+            &[0x04, 0xDE, 0xAD, 0xBE, 0xEF, 0xFF, 0xFF, 0xFF]
+        );
+
+        client
+            .from_frame(&memory_access_response(MemoryAccessStatus::Busy, None))
+            .unwrap();
+        assert!(client.tick(Duration::from_millis(6000)).unwrap().is_none());
+        client
+            .from_frame(&memory_access_response(
+                MemoryAccessStatus::OperationCompleted,
+                None,
+            ))
+            .unwrap();
+
+        let frame = client.tick(Duration::from_millis(6100)).unwrap().unwrap();
+        assert_eq!(
+            frame.pdu(),
+            &[0x04, 0x09, 0x00, 0x10, 0x00, 0x00, 0xFF, 0xFF]
+        );
+        assert!(client.is_complete());
+    }
+
+    #[test]
+    fn memory_access_failure() {
+        let request = MemoryAccessRequest {
+            length: 1,
+            pointer_type: PointerType::Spatial,
+            command: MemoryAccessCommand::Erase,
+            pointer: 0x20,
+            pointer_extension: 0,
+            key: None,
+        };
+
+        let mut client = MemoryAccessClient::new(0xF9, 0x00, request);
+        client.tick(Duration::from_millis(0)).unwrap().unwrap();
+
+        assert_eq!(
+            client.from_frame(&memory_access_response(
+                MemoryAccessStatus::OperationFailed,
+                None
+            )),
+            Err(MemoryAccessError::OperationFailed(None))
+        );
+        assert_eq!(
+            client.state(),
+            MemoryAccessClientState::Failed(MemoryAccessError::OperationFailed(None))
+        );
+
+        let mut client = MemoryAccessClient::new(0xF9, 0x00, request);
+        client.tick(Duration::from_millis(0)).unwrap().unwrap();
+
+        assert!(client.tick(Duration::from_millis(100)).unwrap().is_none());
+        assert_eq!(client.state(), MemoryAccessClientState::Response);
+        assert!(matches!(
+            client.tick(Duration::from_millis(1350)),
+            Err(MemoryAccessError::Timeout)
+        ));
+        assert_eq!(
+            client.state(),
+            MemoryAccessClientState::Failed(MemoryAccessError::Timeout)
+        );
+    }
+
+    #[test]
+    fn memory_access_transfer_timeout() {
+        let mut client = MemoryAccessClient::new(
+            0xF9,
+            0x00,
+            MemoryAccessRequest {
+                length: 20,
+                pointer_type: PointerType::Direct,
+                command: MemoryAccessCommand::Read,
+                pointer: 0x1000,
+                pointer_extension: 0,
+                key: None,
+            },
+        );
+
+        client.tick(Duration::from_millis(0)).unwrap().unwrap();
+        client
+            .from_frame(&memory_access_response(MemoryAccessStatus::Proceed, None))
+            .unwrap();
+
+        let pdu = [0; 21];
+        let mut ecu = ConnectionTransmitter::new(0x00, 0xF9, PGN::DiagnosticMessage16)
+            .with_data(&pdu)
+            .unwrap();
+
+        client
+            .from_frame(&ecu.tick(Duration::from_millis(0)).unwrap())
+            .unwrap();
+        ecu.from_frame(&client.tick(Duration::from_millis(0)).unwrap().unwrap())
+            .unwrap();
+
+        assert!(client.tick(Duration::from_millis(1000)).unwrap().is_none());
+        assert!(matches!(
+            client.tick(Duration::from_millis(1300)),
+            Err(MemoryAccessError::Transport(TransportError::Aborted(
+                AbortReason::Timeout
+            )))
+        ));
+
+        let frame = client.tick(Duration::from_millis(1301)).unwrap().unwrap();
+        assert_eq!(frame.id().pgn(), PGN::TransportProtocolConnectionManagement);
+        assert_eq!(frame.pdu()[..2], [0xFF, 0x03]);
+        assert_eq!(
+            client.state(),
+            MemoryAccessClientState::Failed(MemoryAccessError::Transport(TransportError::Aborted(
+                AbortReason::Timeout
+            )))
+        );
+    }
+
+    #[test]
+    fn diagnostic_message_19() {
+        let mut pdu = [0x00; 40];
+        pdu[..4].copy_from_slice(&[0x78, 0x56, 0x34, 0x12]);
+        pdu[4..12].copy_from_slice(b"CAL12345");
+        pdu[20..24].copy_from_slice(&[0xEF, 0xBE, 0xAD, 0xDE]);
+        pdu[24..40].copy_from_slice(b"ABCDEFGHIJKLMNOP");
+
+        let mut calibrations = Calibrations::from_pdu(&pdu);
+
+        let calibration = calibrations.next().unwrap();
+        assert_eq!(calibration.verification_number, 0x12345678);
+        assert_eq!(calibration.identification, b"CAL12345");
+        assert_eq!(calibration.to_pdu().unwrap(), pdu[..20]);
+
+        let calibration = calibrations.next().unwrap();
+        assert_eq!(calibration.verification_number, 0xDEADBEEF);
+        assert_eq!(calibration.identification, b"ABCDEFGHIJKLMNOP");
+
+        assert!(calibrations.next().is_none());
+        assert_eq!(Calibrations::from_pdu(&pdu[..39]).count(), 1);
+    }
+
+    #[test]
+    fn diagnostic_message_20() {
+        let pdu = [
+            0x0A, 0x00, 0x05, 0x00, 0xCA, 0x0D, 0x1F, 0x03, 0x00, 0x05, 0x00, 0x64, 0x0E, 0x1F,
+            0xFF, 0xFF, 0xFF, 0xFF,
+        ];
+
+        let message = MonitorPerformanceRatio::from_pdu(&pdu).unwrap();
+        assert_eq!(message.ignition_cycles, Some(10));
+        assert_eq!(message.monitoring_conditions_encountered, Some(5));
+
+        let mut ratios = message.ratios();
+
+        let ratio = ratios.next().unwrap();
+        assert_eq!(ratio.suspect_parameter_number, 3530);
+        assert_eq!(ratio.numerator, Some(3));
+        assert_eq!(ratio.denominator, Some(5));
+        assert_eq!(ratio.to_pdu(), pdu[4..11]);
+
+        let ratio = ratios.next().unwrap();
+        assert_eq!(ratio.suspect_parameter_number, 3684);
+        assert_eq!(ratio.numerator, None);
+        assert_eq!(ratio.denominator, None);
+
+        assert!(ratios.next().is_none());
+        assert!(MonitorPerformanceRatio::from_pdu(&pdu[..3]).is_none());
+    }
+
+    #[test]
+    fn diagnostic_message_20_ratio() {
+        let pdu = [0x64, 0x0E, 0x1F, 0xFF, 0xFF, 0x05, 0x00];
+
+        let ratio = MonitorRatio::from_pdu(&pdu).unwrap();
+        assert_eq!(ratio.suspect_parameter_number, 3684);
+        assert_eq!(ratio.numerator, None);
+        assert_eq!(ratio.denominator, Some(5));
+        assert_eq!(ratio.to_pdu(), pdu);
+
+        assert!(MonitorRatio::from_pdu(&pdu[..6]).is_none());
     }
 }
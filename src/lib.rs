@@ -4,6 +4,7 @@
 
 pub mod diagnostic;
 mod name;
+pub mod network;
 mod pgn;
 pub mod protocol;
 mod sa;
@@ -64,6 +64,44 @@ impl Name {
             arbitrary_address: arbitrary_address != 0,
         }
     }
+
+    /// Construct name from a byte slice.
+    ///
+    /// Returns `None` if the slice is shorter than a NAME.
+    pub fn from_slice(bytes: &[u8]) -> Option<Self> {
+        let bytes: [u8; PDU_MAX_LENGTH] = bytes.get(..PDU_MAX_LENGTH)?.try_into().ok()?;
+
+        Some(Self::from_bytes(bytes))
+    }
+
+    /// Returns the NAME as a 64-bit integer.
+    pub fn to_u64(self) -> u64 {
+        u64::from_le_bytes(self.to_bytes())
+    }
+
+    /// Construct name from a 64-bit integer.
+    pub fn from_u64(value: u64) -> Self {
+        Self::from_bytes(value.to_le_bytes())
+    }
+
+    /// Returns `true` if this NAME wins the arbitration for an address from `other`.
+    ///
+    /// The NAME with the lowest numerical value has the highest priority.
+    pub fn has_priority_over(&self, other: &Name) -> bool {
+        self < other
+    }
+}
+
+impl PartialOrd for Name {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Name {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.to_u64().cmp(&other.to_u64())
+    }
 }
 
 impl core::fmt::Display for Name {
@@ -263,4 +301,41 @@ mod tests {
 
         assert_eq!(bytes, [0x01, 0x00, 0xE0, 0xE2, 0x09, 0x3A, 0x12, 0x00]);
     }
+
+    #[test]
+    fn test_name_to_u64() {
+        let name = Name::from_bytes([0x09, 0x03, 0x4B, 0x24, 0x11, 0x05, 0x0C, 0x85]);
+
+        assert_eq!(name.to_u64(), 0x850C0511244B0309);
+        assert_eq!(Name::from_u64(0x850C0511244B0309), name);
+    }
+
+    #[test]
+    fn test_name_from_slice() {
+        let bytes = [0x19, 0xA4, 0x49, 0x24, 0x11, 0x05, 0x0C, 0x85, 0xFF];
+
+        assert_eq!(
+            Name::from_slice(&bytes),
+            Some(Name::from_bytes([
+                0x19, 0xA4, 0x49, 0x24, 0x11, 0x05, 0x0C, 0x85
+            ]))
+        );
+        assert_eq!(Name::from_slice(&bytes[..7]), None);
+    }
+
+    #[test]
+    fn test_name_ordering() {
+        let name0 = NameBuilder::default().identity_number(0x10).build();
+        let name1 = NameBuilder::default().identity_number(0x20).build();
+        let name2 = NameBuilder::default()
+            .identity_number(0x01)
+            .arbitrary_address(true)
+            .build();
+
+        assert!(name0 < name1);
+        assert!(name1 < name2);
+        assert!(name0.has_priority_over(&name1));
+        assert!(!name2.has_priority_over(&name1));
+        assert!(!name0.has_priority_over(&name0));
+    }
 }
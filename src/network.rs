@@ -0,0 +1,825 @@
+use core::{ops::RangeInclusive, time::Duration};
+
+use crate::{
+    protocol::{self, CommandedAddress},
+    transport::Timer,
+    Frame, Name, PGN,
+};
+
+/// Null address, used by nodes that cannot claim an address.
+pub const ADDRESS_NULL: u8 = 0xfe;
+/// Global address.
+pub const ADDRESS_GLOBAL: u8 = 0xff;
+
+/// Time to wait for contention after an address claim.
+pub const ADDRESS_CLAIM_TIMEOUT: Duration = Duration::from_millis(250);
+/// Unit of the random transmit delay of a cannot claim address message.
+const TRANSMIT_DELAY_UNIT: Duration = Duration::from_micros(600);
+
+/// Self-configurable address range.
+pub const SELF_CONFIGURABLE_ADDRESS_RANGE: RangeInclusive<u8> = 128..=247;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AddressClaimState {
+    /// Address claim is pending.
+    Unclaimed,
+    /// Address was claimed, waiting for contention.
+    Claiming,
+    /// Address is claimed.
+    Claimed,
+    /// No address could be claimed.
+    CannotClaim,
+}
+
+/// Address claim procedure.
+///
+/// The claimer implements the address claim procedure of J1939-81. It claims the preferred
+/// address on the first tick and waits for contention. When another node claims the same
+/// address the NAMEs decide which node keeps the address, the node with the lowest NAME wins.
+/// Nodes capable of arbitrary addressing move to the next free address in the address range,
+/// other nodes send a cannot claim address message after a pseudo-random delay.
+pub struct AddressClaimer {
+    name: Name,
+    preferred_address: u8,
+    address: u8,
+    range: RangeInclusive<u8>,
+    state: AddressClaimState,
+    occupied: [u32; 8],
+    pending: Option<Frame>,
+    delay: Timer,
+    timer: Timer,
+}
+
+impl AddressClaimer {
+    pub fn new(name: Name, preferred_address: u8) -> Self {
+        Self {
+            name,
+            preferred_address,
+            address: preferred_address,
+            range: if name.arbitrary_address {
+                SELF_CONFIGURABLE_ADDRESS_RANGE
+            } else {
+                preferred_address..=preferred_address
+            },
+            state: AddressClaimState::Unclaimed,
+            occupied: [0; 8],
+            pending: None,
+            delay: Timer::new(),
+            timer: Timer::new(),
+        }
+    }
+
+    /// Set the address range to pick an address from on contention.
+    ///
+    /// Only nodes capable of arbitrary addressing leave the preferred address. The null and
+    /// global addresses are excluded from the range.
+    pub fn with_range(mut self, range: RangeInclusive<u8>) -> Self {
+        self.range = *range.start()..=(*range.end()).min(ADDRESS_NULL - 1);
+        self
+    }
+
+    /// Returns the NAME of the node.
+    #[inline]
+    pub fn name(&self) -> &Name {
+        &self.name
+    }
+
+    /// Returns the current address of the node.
+    ///
+    /// The null address is returned if the node cannot claim an address.
+    #[inline]
+    pub fn address(&self) -> u8 {
+        self.address
+    }
+
+    /// Returns the current state of the address claim.
+    #[inline]
+    pub fn state(&self) -> AddressClaimState {
+        self.state
+    }
+
+    /// Returns `true` if the address is claimed.
+    #[inline]
+    pub fn is_claimed(&self) -> bool {
+        self.state == AddressClaimState::Claimed
+    }
+
+    /// Pseudo-random transmit delay derived from the NAME.
+    fn transmit_delay(&self) -> Duration {
+        let seed = self
+            .name
+            .to_bytes()
+            .iter()
+            .fold(0u8, |seed, byte| seed ^ byte);
+
+        TRANSMIT_DELAY_UNIT * seed as u32
+    }
+
+    fn is_occupied(&self, address: u8) -> bool {
+        self.occupied[address as usize / 32] & (1 << (address % 32)) != 0
+    }
+
+    fn set_occupied(&mut self, address: u8) {
+        self.occupied[address as usize / 32] |= 1 << (address % 32);
+    }
+
+    /// Restart the address claim at the preferred address.
+    ///
+    /// The address is claimed on the next tick. Addresses claimed by other nodes during the
+    /// previous claim are forgotten, for example to retry after no address could be claimed.
+    pub fn restart(&mut self) {
+        self.address = self.preferred_address;
+        self.state = AddressClaimState::Unclaimed;
+        self.occupied = [0; 8];
+        self.pending = None;
+        self.delay.disarm();
+        self.timer.disarm();
+    }
+
+    fn claim(&mut self, address: u8) {
+        self.address = address;
+        self.state = AddressClaimState::Claiming;
+        self.pending = Some(protocol::address_claimed(address, &self.name));
+        self.delay.disarm();
+        self.timer.arm(ADDRESS_CLAIM_TIMEOUT);
+    }
+
+    fn cannot_claim(&mut self) {
+        self.address = ADDRESS_NULL;
+        self.state = AddressClaimState::CannotClaim;
+        self.pending = Some(protocol::address_claimed(ADDRESS_NULL, &self.name));
+        self.delay.arm(self.transmit_delay());
+        self.timer.disarm();
+    }
+
+    /// Find the next free address in the address range after the current address.
+    fn next_address(&self) -> Option<u8> {
+        if !self.name.arbitrary_address {
+            return None;
+        }
+
+        let start = *self.range.start();
+        let length = self.range.len();
+        let offset = self.address.wrapping_sub(start) as usize;
+
+        (1..=length)
+            .map(|step| start.wrapping_add(((offset + step) % length) as u8))
+            .find(|address| !self.is_occupied(*address))
+    }
+
+    /// Advance the address claim to the current time.
+    ///
+    /// Returns the next frame to send, if any.
+    pub fn tick(&mut self, now: Duration) -> Option<Frame> {
+        if self.state == AddressClaimState::Unclaimed {
+            self.claim(self.address);
+        }
+
+        if self.state == AddressClaimState::Claiming && self.timer.is_expired(now) {
+            self.timer.disarm();
+            self.state = AddressClaimState::Claimed;
+        }
+
+        if self.delay.is_armed() && !self.delay.is_expired(now) {
+            return None;
+        }
+
+        self.delay.disarm();
+        self.pending.take()
+    }
+
+    /// Process an incoming frame.
+    ///
+    /// Address claims and requests for the address claim are handled, all other frames are
+    /// ignored. Frames to send in response are returned from `tick`.
+    pub fn from_frame(&mut self, frame: &Frame) {
+        match frame.id().pgn() {
+            PGN::AddressClaimed => {
+                let name = match Name::from_slice(frame.pdu()) {
+                    Some(name) => name,
+                    None => return,
+                };
+                let sa = frame.id().source_address();
+                if name == self.name || sa == ADDRESS_NULL {
+                    return;
+                }
+
+                self.set_occupied(sa);
+
+                if sa != self.address
+                    || !matches!(
+                        self.state,
+                        AddressClaimState::Claiming | AddressClaimState::Claimed
+                    )
+                {
+                    return;
+                }
+
+                if self.name.has_priority_over(&name) {
+                    self.pending = Some(protocol::address_claimed(self.address, &self.name));
+                } else if let Some(address) = self.next_address() {
+                    self.claim(address);
+                } else {
+                    self.cannot_claim();
+                }
+            }
+            PGN::Request => {
+                if frame.len() < 3 || protocol::request_from_pdu(frame.pdu()) != PGN::AddressClaimed
+                {
+                    return;
+                }
+
+                let da = frame.id().destination_address();
+                if da != Some(ADDRESS_GLOBAL) && da != Some(self.address) {
+                    return;
+                }
+
+                match self.state {
+                    AddressClaimState::Claiming | AddressClaimState::Claimed => {
+                        self.pending = Some(protocol::address_claimed(self.address, &self.name));
+                    }
+                    AddressClaimState::CannotClaim => {
+                        self.pending = Some(protocol::address_claimed(ADDRESS_NULL, &self.name));
+                        self.delay.arm(self.transmit_delay());
+                    }
+                    AddressClaimState::Unclaimed => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Process a reassembled commanded address message.
+    ///
+    /// If the message commands this node, the node claims the new address. The address claim
+    /// is returned from `tick`. Commands for other nodes or to an invalid address are ignored.
+    pub fn from_commanded_address(&mut self, data: &[u8]) {
+        let command = match CommandedAddress::from_pdu(data) {
+            Some(command) => command,
+            None => return,
+        };
+
+        if command.name != self.name || command.address >= ADDRESS_NULL {
+            return;
+        }
+
+        self.occupied = [0; 8];
+        self.claim(command.address);
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NetworkEvent {
+    /// Node claimed an address.
+    NodeAdded {
+        /// Claimed address.
+        address: u8,
+        /// NAME of the node.
+        name: Name,
+    },
+    /// Known node moved to another address.
+    AddressChanged {
+        /// NAME of the node.
+        name: Name,
+        /// Previous address.
+        from: u8,
+        /// New address.
+        to: u8,
+    },
+    /// Node lost its address.
+    NodeLost {
+        /// Previous address.
+        address: u8,
+        /// NAME of the node.
+        name: Name,
+    },
+}
+
+/// Events of a single address table update.
+///
+/// The first event reports the node of the processed message. The second event reports the
+/// node pushed out of its address by a claim with a higher priority NAME, if any.
+pub type NetworkEvents = [Option<NetworkEvent>; 2];
+
+/// Network address table.
+///
+/// The table tracks the NAME of every node on the network by its source address. The table
+/// is updated from address claims, cannot claim address messages and commanded addresses,
+/// every change is reported as a `NetworkEvent`.
+pub struct AddressTable {
+    nodes: [Option<Name>; 256],
+}
+
+impl AddressTable {
+    pub fn new() -> Self {
+        Self { nodes: [None; 256] }
+    }
+
+    /// Returns the NAME of the node at the address.
+    pub fn name(&self, address: u8) -> Option<&Name> {
+        self.nodes[address as usize].as_ref()
+    }
+
+    /// Returns the address of the node with the NAME.
+    pub fn address(&self, name: &Name) -> Option<u8> {
+        self.nodes
+            .iter()
+            .position(|node| node.as_ref() == Some(name))
+            .map(|address| address as u8)
+    }
+
+    /// Returns an iterator over the address and NAME of every known node.
+    pub fn iter(&self) -> impl Iterator<Item = (u8, &Name)> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter_map(|(address, node)| node.as_ref().map(|name| (address as u8, name)))
+    }
+
+    /// Returns the number of known nodes.
+    pub fn len(&self) -> usize {
+        self.nodes.iter().filter(|node| node.is_some()).count()
+    }
+
+    /// Returns `true` if no nodes are known.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.iter().all(|node| node.is_none())
+    }
+
+    /// Remove the node at the address.
+    ///
+    /// Returns the NAME of the removed node.
+    pub fn remove(&mut self, address: u8) -> Option<Name> {
+        self.nodes[address as usize].take()
+    }
+
+    /// Remove all nodes.
+    pub fn clear(&mut self) {
+        self.nodes = [None; 256];
+    }
+
+    /// Create the global request for address claim.
+    ///
+    /// Every node on the network responds with its address claim.
+    pub fn request_address_claim(sa: u8) -> Frame {
+        protocol::request(ADDRESS_GLOBAL, sa, PGN::AddressClaimed)
+    }
+
+    fn update(&mut self, address: u8, name: Name) -> NetworkEvents {
+        if self.nodes[address as usize] == Some(name) {
+            return [None, None];
+        }
+
+        let previous = self.address(&name);
+        if let Some(previous) = previous {
+            self.nodes[previous as usize] = None;
+        }
+
+        let displaced =
+            self.nodes[address as usize]
+                .replace(name)
+                .map(|displaced| NetworkEvent::NodeLost {
+                    address,
+                    name: displaced,
+                });
+
+        let event = match previous {
+            Some(from) => NetworkEvent::AddressChanged {
+                name,
+                from,
+                to: address,
+            },
+            None => NetworkEvent::NodeAdded { address, name },
+        };
+
+        [Some(event), displaced]
+    }
+
+    /// Process an incoming frame.
+    ///
+    /// Address claims and cannot claim address messages are handled, all other frames are
+    /// ignored. A claim for an address already taken by a node with a higher priority NAME is
+    /// ignored, since that node will defend its address. The node displaced by a claim with a
+    /// higher priority NAME is reported as lost in the second event.
+    pub fn from_frame(&mut self, frame: &Frame) -> NetworkEvents {
+        if frame.id().pgn() != PGN::AddressClaimed {
+            return [None, None];
+        }
+
+        let name = match Name::from_slice(frame.pdu()) {
+            Some(name) => name,
+            None => return [None, None],
+        };
+        let sa = frame.id().source_address();
+
+        if sa == ADDRESS_NULL {
+            let address = match self.address(&name) {
+                Some(address) => address,
+                None => return [None, None],
+            };
+            self.nodes[address as usize] = None;
+
+            return [Some(NetworkEvent::NodeLost { address, name }), None];
+        } else if sa == ADDRESS_GLOBAL {
+            return [None, None];
+        }
+
+        if let Some(current) = self.nodes[sa as usize] {
+            if current.has_priority_over(&name) {
+                return [None, None];
+            }
+        }
+
+        self.update(sa, name)
+    }
+
+    /// Process a reassembled commanded address message.
+    ///
+    /// The message carries the NAME of the commanded node followed by its new address.
+    pub fn from_commanded_address(&mut self, data: &[u8]) -> NetworkEvents {
+        let command = match CommandedAddress::from_pdu(data) {
+            Some(command) if command.address < ADDRESS_NULL => command,
+            _ => return [None, None],
+        };
+
+        self.update(command.address, command.name)
+    }
+}
+
+impl Default for AddressTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{transport::ReassemblyManager, FrameBuilder, IdBuilder, NameBuilder};
+
+    use super::*;
+
+    fn address_claimed(sa: u8, identity_number: u32) -> Frame {
+        protocol::address_claimed(
+            sa,
+            &NameBuilder::default()
+                .identity_number(identity_number)
+                .build(),
+        )
+    }
+
+    #[test]
+    fn address_claim_1() {
+        let name = NameBuilder::default().identity_number(0x10).build();
+        let mut claimer = AddressClaimer::new(name, 0x80);
+
+        let frame = claimer.tick(Duration::from_millis(0)).unwrap();
+        assert_eq!(frame.id().as_raw(), 0x18EEFF80);
+        assert_eq!(frame.pdu(), &name.to_bytes());
+        assert_eq!(claimer.state(), AddressClaimState::Claiming);
+
+        assert!(claimer.tick(Duration::from_millis(100)).is_none());
+        assert!(!claimer.is_claimed());
+        assert!(claimer.tick(Duration::from_millis(350)).is_none());
+        assert!(claimer.is_claimed());
+        assert_eq!(claimer.address(), 0x80);
+    }
+
+    #[test]
+    fn address_claim_defend() {
+        let name = NameBuilder::default().identity_number(0x10).build();
+        let mut claimer = AddressClaimer::new(name, 0x80);
+
+        claimer.tick(Duration::from_millis(0)).unwrap();
+        claimer.from_frame(&address_claimed(0x80, 0x20));
+
+        let frame = claimer.tick(Duration::from_millis(10)).unwrap();
+        assert_eq!(frame.id().source_address(), 0x80);
+        assert_eq!(frame.pdu(), &name.to_bytes());
+
+        claimer.tick(Duration::from_millis(300));
+        assert!(claimer.is_claimed());
+        assert_eq!(claimer.address(), 0x80);
+    }
+
+    #[test]
+    fn address_claim_arbitrary_address() {
+        let name = NameBuilder::default()
+            .identity_number(0x20)
+            .arbitrary_address(true)
+            .build();
+        let mut claimer = AddressClaimer::new(name, 0x80).with_range(0x80..=0x82);
+
+        claimer.from_frame(&address_claimed(0x81, 0x30));
+        claimer.tick(Duration::from_millis(0)).unwrap();
+        claimer.from_frame(&address_claimed(0x80, 0x10));
+
+        let frame = claimer.tick(Duration::from_millis(10)).unwrap();
+        assert_eq!(frame.id().source_address(), 0x82);
+        assert_eq!(claimer.state(), AddressClaimState::Claiming);
+
+        claimer.from_frame(&address_claimed(0x82, 0x15));
+
+        // Transmit delay is 0.6 ms times 0xA0.
+        assert!(claimer.tick(Duration::from_millis(20)).is_none());
+
+        let frame = claimer.tick(Duration::from_millis(120)).unwrap();
+        assert_eq!(frame.id().source_address(), ADDRESS_NULL);
+        assert_eq!(claimer.state(), AddressClaimState::CannotClaim);
+        assert_eq!(claimer.address(), ADDRESS_NULL);
+    }
+
+    #[test]
+    fn address_claim_range_excludes_null_and_global() {
+        let name = NameBuilder::default()
+            .identity_number(0x20)
+            .arbitrary_address(true)
+            .build();
+        let mut claimer = AddressClaimer::new(name, 0xFC).with_range(0xFC..=0xFF);
+
+        claimer.tick(Duration::from_millis(0)).unwrap();
+        claimer.from_frame(&address_claimed(0xFC, 0x10));
+
+        let frame = claimer.tick(Duration::from_millis(10)).unwrap();
+        assert_eq!(frame.id().source_address(), 0xFD);
+
+        claimer.from_frame(&address_claimed(0xFD, 0x10));
+        assert_eq!(claimer.state(), AddressClaimState::CannotClaim);
+        assert_eq!(claimer.address(), ADDRESS_NULL);
+    }
+
+    #[test]
+    fn address_claim_restart() {
+        let name = NameBuilder::default()
+            .identity_number(0x20)
+            .arbitrary_address(true)
+            .build();
+        let mut claimer = AddressClaimer::new(name, 0x80).with_range(0x80..=0x81);
+
+        claimer.from_frame(&address_claimed(0x81, 0x10));
+        claimer.tick(Duration::from_millis(0)).unwrap();
+        claimer.from_frame(&address_claimed(0x80, 0x10));
+        assert_eq!(claimer.state(), AddressClaimState::CannotClaim);
+
+        claimer.restart();
+        assert_eq!(claimer.state(), AddressClaimState::Unclaimed);
+
+        let frame = claimer.tick(Duration::from_millis(1000)).unwrap();
+        assert_eq!(frame.id().source_address(), 0x80);
+
+        claimer.from_frame(&address_claimed(0x80, 0x10));
+        let frame = claimer.tick(Duration::from_millis(1010)).unwrap();
+        assert_eq!(frame.id().source_address(), 0x81);
+        assert_eq!(claimer.state(), AddressClaimState::Claiming);
+    }
+
+    #[test]
+    fn address_claim_cannot_claim() {
+        let name = NameBuilder::default().identity_number(0x20).build();
+        let mut claimer = AddressClaimer::new(name, 0x80);
+
+        claimer.tick(Duration::from_millis(0)).unwrap();
+        claimer.from_frame(&address_claimed(0x80, 0x10));
+        assert_eq!(claimer.state(), AddressClaimState::CannotClaim);
+
+        // Transmit delay is 0.6 ms times 0x20.
+        assert!(claimer.tick(Duration::from_millis(10)).is_none());
+        assert!(claimer.tick(Duration::from_millis(25)).is_none());
+
+        let frame = claimer.tick(Duration::from_millis(30)).unwrap();
+        assert_eq!(frame.id().as_raw(), 0x18EEFFFE);
+        assert_eq!(frame.pdu(), &name.to_bytes());
+    }
+
+    #[test]
+    fn address_claim_request() {
+        let name = NameBuilder::default().identity_number(0x10).build();
+        let mut claimer = AddressClaimer::new(name, 0x80);
+
+        claimer.from_frame(&protocol::request(
+            ADDRESS_GLOBAL,
+            0x20,
+            PGN::AddressClaimed,
+        ));
+        assert!(claimer.tick(Duration::from_millis(0)).is_some());
+        assert!(claimer.tick(Duration::from_millis(300)).is_none());
+        assert!(claimer.is_claimed());
+
+        claimer.from_frame(&protocol::request(0x81, 0x20, PGN::AddressClaimed));
+        assert!(claimer.tick(Duration::from_millis(310)).is_none());
+
+        claimer.from_frame(&protocol::request(0x80, 0x20, PGN::SoftwareIdentification));
+        assert!(claimer.tick(Duration::from_millis(320)).is_none());
+
+        claimer.from_frame(&protocol::request(0x80, 0x20, PGN::AddressClaimed));
+        let frame = claimer.tick(Duration::from_millis(330)).unwrap();
+        assert_eq!(frame.id().as_raw(), 0x18EEFF80);
+    }
+
+    #[test]
+    fn address_claim_ignore_other_address() {
+        let name = NameBuilder::default().identity_number(0x20).build();
+        let mut claimer = AddressClaimer::new(name, 0x80);
+
+        claimer.tick(Duration::from_millis(0)).unwrap();
+        claimer.from_frame(&address_claimed(0x81, 0x10));
+        claimer.from_frame(
+            &FrameBuilder::new(IdBuilder::from_pgn(PGN::AddressClaimed).sa(0x80).build())
+                .copy_from_slice(&[0; 4])
+                .build(),
+        );
+
+        assert!(claimer.tick(Duration::from_millis(10)).is_none());
+        assert_eq!(claimer.state(), AddressClaimState::Claiming);
+    }
+
+    #[test]
+    fn address_claim_commanded_address() {
+        let name = NameBuilder::default().identity_number(0x10).build();
+        let other = NameBuilder::default().identity_number(0x20).build();
+        let mut claimer = AddressClaimer::new(name, 0x80);
+
+        claimer.tick(Duration::from_millis(0)).unwrap();
+        claimer.tick(Duration::from_millis(300));
+        assert!(claimer.is_claimed());
+
+        let mut manager = ReassemblyManager::<1>::new(0x80);
+        let mut command = None;
+        for frame in protocol::commanded_address(0x20, &name, 0x90) {
+            if let Some((_, pgn, data)) = manager.from_frame(&frame).unwrap() {
+                assert_eq!(pgn, PGN::CommandedAddress);
+                command = CommandedAddress::from_pdu(data);
+            }
+        }
+
+        let command = command.unwrap();
+        assert_eq!(command.name, name);
+        assert_eq!(command.address, 0x90);
+
+        claimer.from_commanded_address(
+            &CommandedAddress {
+                name: other,
+                address: 0x91,
+            }
+            .to_pdu(),
+        );
+        claimer.from_commanded_address(
+            &CommandedAddress {
+                name,
+                address: ADDRESS_NULL,
+            }
+            .to_pdu(),
+        );
+        assert!(claimer.tick(Duration::from_millis(310)).is_none());
+        assert_eq!(claimer.address(), 0x80);
+
+        claimer.from_commanded_address(&command.to_pdu());
+        let frame = claimer.tick(Duration::from_millis(320)).unwrap();
+        assert_eq!(frame.id().as_raw(), 0x18EEFF90);
+        assert_eq!(frame.pdu(), &name.to_bytes());
+        assert_eq!(claimer.state(), AddressClaimState::Claiming);
+
+        claimer.tick(Duration::from_millis(600));
+        assert!(claimer.is_claimed());
+        assert_eq!(claimer.address(), 0x90);
+    }
+
+    #[test]
+    fn address_table_1() {
+        let name0 = NameBuilder::default().identity_number(0x10).build();
+        let name1 = NameBuilder::default().identity_number(0x20).build();
+
+        let mut table = AddressTable::new();
+        assert!(table.is_empty());
+
+        assert_eq!(
+            table.from_frame(&protocol::address_claimed(0x28, &name0)),
+            [
+                Some(NetworkEvent::NodeAdded {
+                    address: 0x28,
+                    name: name0
+                }),
+                None
+            ]
+        );
+        assert_eq!(
+            table.from_frame(&protocol::address_claimed(0x28, &name0)),
+            [None, None]
+        );
+        assert_eq!(
+            table.from_frame(&protocol::address_claimed(0x30, &name1)),
+            [
+                Some(NetworkEvent::NodeAdded {
+                    address: 0x30,
+                    name: name1
+                }),
+                None
+            ]
+        );
+
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.name(0x28), Some(&name0));
+        assert_eq!(table.address(&name1), Some(0x30));
+        assert_eq!(table.name(0x29), None);
+
+        let mut nodes = table.iter();
+        assert_eq!(nodes.next(), Some((0x28, &name0)));
+        assert_eq!(nodes.next(), Some((0x30, &name1)));
+        assert_eq!(nodes.next(), None);
+    }
+
+    #[test]
+    fn address_table_contention() {
+        let name0 = NameBuilder::default().identity_number(0x10).build();
+        let name1 = NameBuilder::default().identity_number(0x20).build();
+
+        let mut table = AddressTable::new();
+        table.from_frame(&protocol::address_claimed(0x80, &name1));
+
+        assert_eq!(
+            table.from_frame(&protocol::address_claimed(0x80, &name0)),
+            [
+                Some(NetworkEvent::NodeAdded {
+                    address: 0x80,
+                    name: name0
+                }),
+                Some(NetworkEvent::NodeLost {
+                    address: 0x80,
+                    name: name1
+                })
+            ]
+        );
+        assert_eq!(table.address(&name1), None);
+        assert_eq!(
+            table.from_frame(&protocol::address_claimed(0x80, &name1)),
+            [None, None]
+        );
+        assert_eq!(
+            table.from_frame(&protocol::address_claimed(0x81, &name1)),
+            [
+                Some(NetworkEvent::NodeAdded {
+                    address: 0x81,
+                    name: name1
+                }),
+                None
+            ]
+        );
+        assert_eq!(
+            table.from_frame(&protocol::address_claimed(0x82, &name1)),
+            [
+                Some(NetworkEvent::AddressChanged {
+                    name: name1,
+                    from: 0x81,
+                    to: 0x82
+                }),
+                None
+            ]
+        );
+        assert_eq!(table.name(0x81), None);
+
+        assert_eq!(
+            table.from_frame(&protocol::address_claimed(ADDRESS_NULL, &name1)),
+            [
+                Some(NetworkEvent::NodeLost {
+                    address: 0x82,
+                    name: name1
+                }),
+                None
+            ]
+        );
+        assert_eq!(table.len(), 1);
+    }
+
+    #[test]
+    fn address_table_commanded_address() {
+        let name = NameBuilder::default().identity_number(0x10).build();
+
+        let mut table = AddressTable::new();
+        table.from_frame(&protocol::address_claimed(0x80, &name));
+
+        let mut data = [0; 9];
+        data[..8].copy_from_slice(&name.to_bytes());
+        data[8] = 0x90;
+
+        assert_eq!(
+            table.from_commanded_address(&data),
+            [
+                Some(NetworkEvent::AddressChanged {
+                    name,
+                    from: 0x80,
+                    to: 0x90
+                }),
+                None
+            ]
+        );
+        assert_eq!(table.from_commanded_address(&data[..8]), [None, None]);
+        assert_eq!(table.address(&name), Some(0x90));
+    }
+
+    #[test]
+    fn address_table_request() {
+        let frame = AddressTable::request_address_claim(0x20);
+
+        assert_eq!(frame.id().as_raw(), 0x18EAFF20);
+        assert_eq!(frame.pdu(), &[0x00, 0xEE, 0x00]);
+    }
+}
@@ -39,6 +39,10 @@ pub enum PGN {
     ProprietarilyConfigurableMessage16,
     /// XFER - Transfer.
     Transfer,
+    /// ETP.DT - Extended Transport Protocol Data Transfer.
+    ExtendedTransportProtocolDataTransfer,
+    /// ETP.CM - Extended Transport Protocol Connection Management.
+    ExtendedTransportProtocolConnectionManagement,
     /// EEC2 - Electronic Engine Controller 2.
     ElectronicEngineController2,
     /// EEC1 - Electronic Engine Controller 1.
@@ -63,10 +67,24 @@ pub enum PGN {
     DiagnosticMessage3,
     /// DM4 - Diagnostic Message 4 - Freeze Frame Parameters.
     DiagnosticMessage4,
+    /// DM5 - Diagnostic Message 5 - Diagnostic Readiness 1.
+    DiagnosticMessage5,
     /// DM11 - Diagnostic Message 11 - Diagnostics Data Clear of Active DTCs.
     DiagnosticMessage11,
     /// DM12 - Emission Related Active DTCs.
     DiagnosticMessage12,
+    /// DM13 - Stop Start Broadcast.
+    DiagnosticMessage13,
+    /// DM14 - Memory Access Request.
+    DiagnosticMessage14,
+    /// DM15 - Memory Access Response.
+    DiagnosticMessage15,
+    /// DM16 - Binary Data Transfer.
+    DiagnosticMessage16,
+    /// DM19 - Calibration Information.
+    DiagnosticMessage19,
+    /// DM20 - Monitor Performance Ratio.
+    DiagnosticMessage20,
     /// SOFT - Software Identification.
     SoftwareIdentification,
     /// EFL/P2 - Engine Fluid Level/Pressure 2.
@@ -199,8 +217,16 @@ impl From<u32> for PGN {
             48_640 => PGN::ProprietarilyConfigurableMessage14,
             48_896 => PGN::ProprietarilyConfigurableMessage15,
             49_152 => PGN::ProprietarilyConfigurableMessage16,
+            49_664 => PGN::DiagnosticMessage20,
+            50_944 => PGN::ExtendedTransportProtocolDataTransfer,
+            51_200 => PGN::ExtendedTransportProtocolConnectionManagement,
             51_456 => PGN::Request2,
             51_712 => PGN::Transfer,
+            54_016 => PGN::DiagnosticMessage19,
+            55_040 => PGN::DiagnosticMessage16,
+            55_296 => PGN::DiagnosticMessage15,
+            55_552 => PGN::DiagnosticMessage14,
+            57_088 => PGN::DiagnosticMessage13,
             59_392 => PGN::AcknowledgmentMessage,
             59_904 => PGN::Request,
             60_160 => PGN::TransportProtocolDataTransfer,
@@ -221,6 +247,7 @@ impl From<u32> for PGN {
             65_227 => PGN::DiagnosticMessage2,
             65_228 => PGN::DiagnosticMessage3,
             65_229 => PGN::DiagnosticMessage4,
+            65_230 => PGN::DiagnosticMessage5,
             65_235 => PGN::DiagnosticMessage11,
             65_236 => PGN::DiagnosticMessage12,
             65_240 => PGN::CommandedAddress,
@@ -283,8 +310,16 @@ impl From<PGN> for u32 {
             PGN::ProprietarilyConfigurableMessage14 => 48_640,
             PGN::ProprietarilyConfigurableMessage15 => 48_896,
             PGN::ProprietarilyConfigurableMessage16 => 49_152,
+            PGN::DiagnosticMessage20 => 49_664,
+            PGN::ExtendedTransportProtocolDataTransfer => 50_944,
+            PGN::ExtendedTransportProtocolConnectionManagement => 51_200,
             PGN::Request2 => 51_456,
             PGN::Transfer => 51_712,
+            PGN::DiagnosticMessage19 => 54_016,
+            PGN::DiagnosticMessage16 => 55_040,
+            PGN::DiagnosticMessage15 => 55_296,
+            PGN::DiagnosticMessage14 => 55_552,
+            PGN::DiagnosticMessage13 => 57_088,
             PGN::AcknowledgmentMessage => 59_392,
             PGN::Request => 59_904,
             PGN::TransportProtocolDataTransfer => 60_160,
@@ -305,6 +340,7 @@ impl From<PGN> for u32 {
             PGN::DiagnosticMessage2 => 65_227,
             PGN::DiagnosticMessage3 => 65_228,
             PGN::DiagnosticMessage4 => 65_229,
+            PGN::DiagnosticMessage5 => 65_230,
             PGN::DiagnosticMessage11 => 65_235,
             PGN::DiagnosticMessage12 => 65_236,
             PGN::CommandedAddress => 65_240,
@@ -1,5 +1,11 @@
+use core::time::Duration;
+
 use crate::{
-    transport::BroadcastTransport, Frame, FrameBuilder, IdBuilder, Name, PDU_NOT_AVAILABLE, PGN,
+    slots,
+    transport::{
+        BroadcastTransmitter, ConnectionTransmitter, Timer, TransportMessage, DATA_MAX_LENGTH,
+    },
+    Frame, FrameBuilder, Id, IdBuilder, Name, PDU_MAX_LENGTH, PDU_NOT_AVAILABLE, PGN,
 };
 
 /// Create PGN request frame.
@@ -18,6 +24,315 @@ pub fn request_from_pdu(pdu: &[u8]) -> PGN {
     PGN::from_le_bytes([pdu[0], pdu[1], pdu[2]])
 }
 
+/// Time to wait for the response to a request.
+pub const REQUEST_TIMEOUT: Duration = Duration::from_millis(1250);
+
+/// Outcome of a request.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RequestEvent {
+    /// Node responded with the requested PGN.
+    Response {
+        /// Address of the responder.
+        address: u8,
+        pgn: PGN,
+    },
+    /// Node responded with an acknowledgement.
+    Acknowledged {
+        /// Address of the responder.
+        address: u8,
+        acknowledgement: Acknowledgement,
+    },
+    /// No response within `REQUEST_TIMEOUT`.
+    Timeout {
+        /// Destination address of the request.
+        da: u8,
+        pgn: PGN,
+    },
+}
+
+struct PendingRequest {
+    da: u8,
+    pgn: PGN,
+    responded: bool,
+    timer: Timer,
+}
+
+/// Request tracker.
+///
+/// The requester records up to `N` outstanding requests and matches the responses to them.
+/// A request to a specific node completes on the first response or acknowledgement from that
+/// node. A request to the global address collects the responses of all nodes until the
+/// timeout. A request without any response reports a timeout from `tick`.
+///
+/// Responses sent with the transport protocol are matched once reassembled, see
+/// `from_message`.
+pub struct Requester<const N: usize> {
+    sa: u8,
+    pending: [Option<PendingRequest>; N],
+}
+
+impl<const N: usize> Requester<N> {
+    pub fn new(sa: u8) -> Self {
+        Self {
+            sa,
+            pending: core::array::from_fn(|_| None),
+        }
+    }
+
+    /// Set the source address, for example after an address claim.
+    pub fn set_address(&mut self, sa: u8) {
+        self.sa = sa;
+    }
+
+    /// Returns the number of outstanding requests.
+    pub fn len(&self) -> usize {
+        self.pending.iter().flatten().count()
+    }
+
+    /// Returns `true` if there are no outstanding requests.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the request for the PGN to the destination address is outstanding.
+    pub fn is_pending(&self, da: u8, pgn: PGN) -> bool {
+        self.pending
+            .iter()
+            .flatten()
+            .any(|request| request.da == da && request.pgn == pgn)
+    }
+
+    /// Create the request frame and record the request sent at the current time.
+    ///
+    /// A request which is already outstanding is restarted. Returns `None` if the requester
+    /// is full.
+    pub fn request(&mut self, da: u8, pgn: PGN, now: Duration) -> Option<Frame> {
+        let index = match self.pending.iter().position(
+            |slot| matches!(slot, Some(request) if request.da == da && request.pgn == pgn),
+        ) {
+            Some(index) => index,
+            None => self.pending.iter().position(|slot| slot.is_none())?,
+        };
+
+        let mut timer = Timer::new();
+        timer.arm_at(now, REQUEST_TIMEOUT);
+
+        self.pending[index] = Some(PendingRequest {
+            da,
+            pgn,
+            responded: false,
+            timer,
+        });
+
+        Some(request(da, self.sa, pgn))
+    }
+
+    /// Forget the request for the PGN to the destination address.
+    pub fn cancel(&mut self, da: u8, pgn: PGN) {
+        for slot in self.pending.iter_mut() {
+            if matches!(slot, Some(request) if request.da == da && request.pgn == pgn) {
+                *slot = None;
+            }
+        }
+    }
+
+    /// Match a response from the address to the outstanding requests.
+    ///
+    /// A request to the address takes precedence over a request to the global address.
+    fn respond(&mut self, address: u8, pgn: PGN) -> bool {
+        if let Some(slot) = self.pending.iter_mut().find(
+            |slot| matches!(slot, Some(request) if request.pgn == pgn && request.da == address),
+        ) {
+            *slot = None;
+            return true;
+        }
+
+        match self
+            .pending
+            .iter_mut()
+            .flatten()
+            .find(|request| request.pgn == pgn && request.da == PDU_NOT_AVAILABLE)
+        {
+            Some(request) => {
+                request.responded = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn response(&mut self, id: &Id, pgn: PGN) -> Option<RequestEvent> {
+        if !matches!(id.destination_address(), None | Some(PDU_NOT_AVAILABLE))
+            && id.destination_address() != Some(self.sa)
+        {
+            return None;
+        }
+
+        let address = id.source_address();
+
+        self.respond(address, pgn)
+            .then_some(RequestEvent::Response { address, pgn })
+    }
+
+    /// Process a frame.
+    ///
+    /// Returns the event if the frame is a response or acknowledgement to an outstanding
+    /// request.
+    pub fn from_frame(&mut self, frame: &Frame) -> Option<RequestEvent> {
+        if frame.id().pgn() != PGN::AcknowledgmentMessage {
+            return self.response(frame.id(), frame.id().pgn());
+        }
+
+        let acknowledgement = Acknowledgement::from_frame(frame)?;
+        if acknowledgement.address != self.sa && acknowledgement.address != PDU_NOT_AVAILABLE {
+            return None;
+        }
+
+        let address = frame.id().source_address();
+
+        self.respond(address, acknowledgement.pgn)
+            .then_some(RequestEvent::Acknowledged {
+                address,
+                acknowledgement,
+            })
+    }
+
+    /// Process a message reassembled by the transport protocol.
+    ///
+    /// Returns the event if the message is a response to an outstanding request.
+    pub fn from_message(&mut self, message: &TransportMessage) -> Option<RequestEvent> {
+        let (id, pgn, _) = message;
+
+        self.response(id, *pgn)
+    }
+
+    /// Advance the requests to the current time.
+    ///
+    /// Returns a timeout event for a request without any response, one request per call.
+    /// Expired requests to the global address which received a response are released.
+    pub fn tick(&mut self, now: Duration) -> Option<RequestEvent> {
+        for slot in self.pending.iter_mut() {
+            let expired = match slot {
+                Some(request) => request.timer.is_expired(now),
+                None => false,
+            };
+
+            if expired {
+                let request = slot.take().unwrap();
+                if !request.responded {
+                    return Some(RequestEvent::Timeout {
+                        da: request.da,
+                        pgn: request.pgn,
+                    });
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Request2 message.
+///
+/// Request2 extends the request with an extended identifier to select a specific instance of
+/// the requested PGN, and can ask the responder to reply with the Transfer PGN.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Request2 {
+    /// Requested PGN.
+    pub pgn: PGN,
+    /// Extended identifier, or special instructions, of the requested PGN.
+    pub extended_identifier: [u8; 3],
+    /// Reply with the Transfer PGN.
+    pub use_transfer: Option<bool>,
+}
+
+impl Request2 {
+    /// Returns `None` if the PDU is shorter than 7 bytes.
+    pub fn from_pdu(pdu: &[u8]) -> Option<Self> {
+        if pdu.len() < 7 {
+            return None;
+        }
+
+        Some(Self {
+            pgn: PGN::from_le_bytes([pdu[0], pdu[1], pdu[2]]),
+            extended_identifier: [pdu[3], pdu[4], pdu[5]],
+            use_transfer: slots::bool_from_value(pdu[6]),
+        })
+    }
+
+    pub fn to_pdu(&self) -> [u8; 8] {
+        let pgn_bytes = self.pgn.to_le_bytes();
+
+        [
+            pgn_bytes[0],
+            pgn_bytes[1],
+            pgn_bytes[2],
+            self.extended_identifier[0],
+            self.extended_identifier[1],
+            self.extended_identifier[2],
+            0xFC | slots::bool_to_value(self.use_transfer),
+            PDU_NOT_AVAILABLE,
+        ]
+    }
+
+    /// Create the request frame from the source address to the destination address.
+    pub fn to_frame(&self, sa: u8, da: u8) -> Frame {
+        let id = IdBuilder::from_pgn(PGN::Request2).sa(sa).da(da).build();
+
+        FrameBuilder::new(id)
+            .copy_from_slice(&self.to_pdu())
+            .build()
+    }
+}
+
+/// Number of bytes in the Transfer PGN header.
+pub const TRANSFER_HEADER_LENGTH: usize = 4;
+
+/// Transfer message.
+///
+/// The Transfer PGN wraps the reply to a Request2 with the use transfer flag set. A gateway
+/// uses it to deliver a message on behalf of another address. Messages longer than a single
+/// frame are sent with the transport protocol.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Transfer<'a> {
+    /// Destination address of the transferred message.
+    pub destination_address: u8,
+    /// PGN of the transferred message.
+    pub pgn: PGN,
+    /// Transferred message data.
+    pub data: &'a [u8],
+}
+
+impl<'a> Transfer<'a> {
+    /// Returns `None` if the PDU is shorter than the header.
+    pub fn from_pdu(pdu: &'a [u8]) -> Option<Self> {
+        if pdu.len() < TRANSFER_HEADER_LENGTH {
+            return None;
+        }
+
+        Some(Self {
+            destination_address: pdu[0],
+            pgn: PGN::from_le_bytes([pdu[1], pdu[2], pdu[3]]),
+            data: &pdu[TRANSFER_HEADER_LENGTH..],
+        })
+    }
+
+    /// Encode the transfer message into the buffer.
+    ///
+    /// Returns the number of bytes written, or `None` if the buffer is too small.
+    pub fn copy_to_slice(&self, pdu: &mut [u8]) -> Option<usize> {
+        let length = TRANSFER_HEADER_LENGTH + self.data.len();
+        let pdu = pdu.get_mut(..length)?;
+        let pgn_bytes = self.pgn.to_le_bytes();
+
+        pdu[0] = self.destination_address;
+        pdu[1..TRANSFER_HEADER_LENGTH].copy_from_slice(&pgn_bytes[..3]);
+        pdu[TRANSFER_HEADER_LENGTH..].copy_from_slice(self.data);
+
+        Some(length)
+    }
+}
+
 /// Create address claimed frame.
 pub fn address_claimed(sa: u8, name: &Name) -> Frame {
     let id = IdBuilder::from_pgn(PGN::AddressClaimed)
@@ -30,50 +345,782 @@ pub fn address_claimed(sa: u8, name: &Name) -> Frame {
         .build()
 }
 
-/// Create acknowledgment frame.
-pub fn acknowledgement(sa: u8, pgn: PGN) -> Frame {
-    let pgn_bytes = pgn.to_le_bytes();
+/// Acknowledgement control.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AcknowledgementControl {
+    /// Positive acknowledgement (ACK).
+    Acknowledged,
+    /// Negative acknowledgement (NACK).
+    NotAcknowledged,
+    /// Access denied.
+    AccessDenied,
+    /// Cannot respond.
+    CannotRespond,
+}
 
-    let id = IdBuilder::from_pgn(PGN::AcknowledgmentMessage)
-        .sa(sa)
-        .da(PDU_NOT_AVAILABLE)
-        .build();
+impl AcknowledgementControl {
+    pub fn from_value(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::Acknowledged),
+            1 => Some(Self::NotAcknowledged),
+            2 => Some(Self::AccessDenied),
+            3 => Some(Self::CannotRespond),
+            _ => None,
+        }
+    }
 
-    FrameBuilder::new(id)
-        .copy_from_slice(&[
-            0x01,
-            PDU_NOT_AVAILABLE,
-            PDU_NOT_AVAILABLE,
+    pub fn to_value(control: Self) -> u8 {
+        match control {
+            Self::Acknowledged => 0,
+            Self::NotAcknowledged => 1,
+            Self::AccessDenied => 2,
+            Self::CannotRespond => 3,
+        }
+    }
+}
+
+/// Acknowledgement message.
+///
+/// The acknowledgement is the response to a request or command. It is sent to the global
+/// address and names the address of the requester.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Acknowledgement {
+    pub control: AcknowledgementControl,
+    /// Group function value of the acknowledged PGN.
+    pub group_function: Option<u8>,
+    /// Address of the requester.
+    pub address: u8,
+    /// Acknowledged PGN.
+    pub pgn: PGN,
+}
+
+impl Acknowledgement {
+    /// Returns `None` if the PDU is too short or the control byte is unknown.
+    pub fn from_pdu(pdu: &[u8]) -> Option<Self> {
+        if pdu.len() != PDU_MAX_LENGTH {
+            return None;
+        }
+
+        Some(Self {
+            control: AcknowledgementControl::from_value(pdu[0])?,
+            group_function: if pdu[1] == PDU_NOT_AVAILABLE {
+                None
+            } else {
+                Some(pdu[1])
+            },
+            address: pdu[4],
+            pgn: PGN::from_le_bytes([pdu[5], pdu[6], pdu[7]]),
+        })
+    }
+
+    /// Returns `None` if the frame is not an acknowledgement.
+    pub fn from_frame(frame: &Frame) -> Option<Self> {
+        if frame.id().pgn() != PGN::AcknowledgmentMessage {
+            return None;
+        }
+
+        Self::from_pdu(frame.pdu())
+    }
+
+    pub fn to_pdu(&self) -> [u8; 8] {
+        let pgn_bytes = self.pgn.to_le_bytes();
+
+        [
+            AcknowledgementControl::to_value(self.control),
+            self.group_function.unwrap_or(PDU_NOT_AVAILABLE),
             PDU_NOT_AVAILABLE,
             PDU_NOT_AVAILABLE,
+            self.address,
             pgn_bytes[0],
             pgn_bytes[1],
             pgn_bytes[2],
-        ])
-        .build()
+        ]
+    }
+
+    /// Create the acknowledgement frame from the source address.
+    pub fn to_frame(&self, sa: u8) -> Frame {
+        let id = IdBuilder::from_pgn(PGN::AcknowledgmentMessage)
+            .sa(sa)
+            .da(PDU_NOT_AVAILABLE)
+            .build();
+
+        FrameBuilder::new(id)
+            .copy_from_slice(&self.to_pdu())
+            .build()
+    }
+}
+
+impl core::fmt::Display for Acknowledgement {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "{:?} PGN: {:?}, Address: {}",
+            self.control, self.pgn, self.address
+        )
+    }
+}
+
+/// Create positive acknowledgment frame.
+///
+/// The control byte is 0 (ACK), see `AcknowledgementControl`.
+pub fn acknowledgement(sa: u8, pgn: PGN) -> Frame {
+    Acknowledgement {
+        control: AcknowledgementControl::Acknowledged,
+        group_function: None,
+        address: PDU_NOT_AVAILABLE,
+        pgn,
+    }
+    .to_frame(sa)
+}
+
+/// Create negative acknowledgment frame.
+///
+/// Used to answer a request from the address for a PGN which is not supported.
+pub fn negative_acknowledgement(sa: u8, address: u8, pgn: PGN) -> Frame {
+    Acknowledgement {
+        control: AcknowledgementControl::NotAcknowledged,
+        group_function: None,
+        address,
+        pgn,
+    }
+    .to_frame(sa)
+}
+
+/// Number of bytes in a commanded address message.
+pub const COMMANDED_ADDRESS_LENGTH: usize = 9;
+
+/// Commanded address message.
+///
+/// Commands the node with the NAME to claim the new address. The message is longer than a
+/// single frame and is sent with the transport protocol.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CommandedAddress {
+    /// NAME of the commanded node.
+    pub name: Name,
+    /// New address of the node.
+    pub address: u8,
+}
+
+impl CommandedAddress {
+    /// Returns `None` if the PDU is shorter than the commanded address message.
+    pub fn from_pdu(pdu: &[u8]) -> Option<Self> {
+        Some(Self {
+            name: Name::from_slice(pdu)?,
+            address: *pdu.get(8)?,
+        })
+    }
+
+    pub fn to_pdu(&self) -> [u8; COMMANDED_ADDRESS_LENGTH] {
+        let name_bytes = self.name.to_bytes();
+
+        [
+            name_bytes[0],
+            name_bytes[1],
+            name_bytes[2],
+            name_bytes[3],
+            name_bytes[4],
+            name_bytes[5],
+            name_bytes[6],
+            name_bytes[7],
+            self.address,
+        ]
+    }
 }
 
 /// Assign address to node.
 pub fn commanded_address(sa: u8, name: &Name, address: u8) -> [Frame; 3] {
-    let name_bytes = name.to_bytes();
-
-    let data = [
-        name_bytes[0],
-        name_bytes[1],
-        name_bytes[2],
-        name_bytes[3],
-        name_bytes[4],
-        name_bytes[5],
-        name_bytes[6],
-        name_bytes[7],
+    let data = CommandedAddress {
+        name: *name,
         address,
-    ];
+    }
+    .to_pdu();
 
-    let mut transport = BroadcastTransport::new(sa, PGN::CommandedAddress).with_data(&data);
+    let mut transport = BroadcastTransmitter::new(sa, PGN::CommandedAddress)
+        .with_data(&data)
+        .unwrap();
 
-    let frame0 = transport.next_frame();
-    let frame1 = transport.next_frame();
-    let frame2 = transport.next_frame();
+    let frame0 = transport.next_frame().unwrap();
+    let frame1 = transport.next_frame().unwrap();
+    let frame2 = transport.next_frame().unwrap();
 
     [frame0, frame1, frame2]
 }
+
+/// Request handler.
+///
+/// The handler encodes the requested message into the buffer and returns the number of bytes
+/// written, or `None` if the message is currently not available. The buffer is the one passed
+/// to `Responder::from_frame`.
+pub type RequestHandler<'a> = &'a mut dyn FnMut(&mut [u8]) -> Option<usize>;
+
+/// Reply to a request.
+pub enum Reply<'b> {
+    /// Reply fits in a single frame.
+    Frame(Frame),
+    /// Reply to a global request with the broadcast transport protocol.
+    Broadcast(BroadcastTransmitter<'b>),
+    /// Reply to the requester with the connection mode transport protocol.
+    Connection(ConnectionTransmitter<'b>),
+}
+
+/// Request responder.
+///
+/// The responder holds up to `N` request handlers, one per PGN. A request for a registered PGN
+/// is answered with the message of the handler. Messages longer than a single frame are sent
+/// with the broadcast transport protocol if the request was sent to the global address, or
+/// with the connection mode transport protocol to the requester otherwise.
+///
+/// A request sent to this node for an unknown PGN is answered with a negative acknowledgement,
+/// and with cannot respond if the handler has no message available. Requests sent to the
+/// global address are never answered with an acknowledgement.
+///
+/// Transport replies borrow the buffer passed to `from_frame`, not the responder, so the
+/// responder can answer other requests while a reply is being sent.
+pub struct Responder<'a, const N: usize> {
+    sa: u8,
+    handlers: [Option<(PGN, RequestHandler<'a>)>; N],
+}
+
+impl<'a, const N: usize> Responder<'a, N> {
+    pub fn new(sa: u8) -> Self {
+        Self {
+            sa,
+            handlers: core::array::from_fn(|_| None),
+        }
+    }
+
+    /// Returns the source address of the responder.
+    #[inline]
+    pub fn address(&self) -> u8 {
+        self.sa
+    }
+
+    /// Set the source address, for example after an address claim.
+    pub fn set_address(&mut self, sa: u8) {
+        self.sa = sa;
+    }
+
+    /// Register the handler for the PGN.
+    ///
+    /// An existing handler for the PGN is replaced. Returns the handler if the responder is
+    /// full.
+    pub fn register(
+        &mut self,
+        pgn: PGN,
+        handler: RequestHandler<'a>,
+    ) -> Result<(), RequestHandler<'a>> {
+        let slot = match self
+            .handlers
+            .iter()
+            .position(|slot| matches!(slot, Some((other, _)) if *other == pgn))
+        {
+            Some(position) => &mut self.handlers[position],
+            None => match self.handlers.iter_mut().find(|slot| slot.is_none()) {
+                Some(slot) => slot,
+                None => return Err(handler),
+            },
+        };
+
+        *slot = Some((pgn, handler));
+
+        Ok(())
+    }
+
+    /// Remove the handler for the PGN.
+    pub fn unregister(&mut self, pgn: PGN) {
+        for slot in self.handlers.iter_mut() {
+            if matches!(slot, Some((other, _)) if *other == pgn) {
+                *slot = None;
+            }
+        }
+    }
+
+    /// Returns `true` if a handler is registered for the PGN.
+    pub fn is_registered(&self, pgn: PGN) -> bool {
+        self.handlers
+            .iter()
+            .any(|slot| matches!(slot, Some((other, _)) if *other == pgn))
+    }
+
+    /// Process a request frame.
+    ///
+    /// Returns the reply to send, if any. The handler encodes the message into the buffer, which
+    /// should hold `DATA_MAX_LENGTH` bytes. A transport reply borrows the buffer until the reply
+    /// is sent. Frames other than requests to this node or the global address are ignored.
+    pub fn from_frame<'b>(&mut self, frame: &Frame, buffer: &'b mut [u8]) -> Option<Reply<'b>> {
+        if frame.id().pgn() != PGN::Request || frame.pdu().len() < 3 {
+            return None;
+        }
+
+        let requester = frame.id().source_address();
+        let da = match frame.id().destination_address() {
+            Some(da) if da == self.sa => requester,
+            Some(PDU_NOT_AVAILABLE) => PDU_NOT_AVAILABLE,
+            _ => return None,
+        };
+
+        let pgn = request_from_pdu(frame.pdu());
+
+        let handler = self
+            .handlers
+            .iter_mut()
+            .flatten()
+            .find(|(other, _)| *other == pgn)
+            .map(|(_, handler)| handler);
+
+        let control = match handler {
+            Some(handler) => match handler(buffer) {
+                Some(length) if length <= DATA_MAX_LENGTH && length <= buffer.len() => {
+                    let buffer: &'b [u8] = buffer;
+                    let data = &buffer[..length];
+
+                    return if data.len() <= PDU_MAX_LENGTH {
+                        let id = IdBuilder::from_pgn(pgn).sa(self.sa).da(da).build();
+
+                        Some(Reply::Frame(
+                            FrameBuilder::new(id).copy_from_slice(data).build(),
+                        ))
+                    } else if da == PDU_NOT_AVAILABLE {
+                        BroadcastTransmitter::new(self.sa, pgn)
+                            .with_data(data)
+                            .ok()
+                            .map(Reply::Broadcast)
+                    } else {
+                        ConnectionTransmitter::new(self.sa, da, pgn)
+                            .with_data(data)
+                            .ok()
+                            .map(Reply::Connection)
+                    };
+                }
+                _ => AcknowledgementControl::CannotRespond,
+            },
+            None => AcknowledgementControl::NotAcknowledged,
+        };
+
+        if da == PDU_NOT_AVAILABLE {
+            return None;
+        }
+
+        Some(Reply::Frame(
+            Acknowledgement {
+                control,
+                group_function: None,
+                address: requester,
+                pgn,
+            }
+            .to_frame(self.sa),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn acknowledgement_1() {
+        let frame = acknowledgement(0x28, PGN::DiagnosticMessage3);
+
+        assert_eq!(frame.id().as_raw(), 0x18E8FF28);
+        assert_eq!(
+            frame.pdu(),
+            &[0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xCC, 0xFE, 0x00]
+        );
+        assert_eq!(
+            Acknowledgement::from_frame(&frame),
+            Some(Acknowledgement {
+                control: AcknowledgementControl::Acknowledged,
+                group_function: None,
+                address: 0xFF,
+                pgn: PGN::DiagnosticMessage3,
+            })
+        );
+    }
+
+    #[test]
+    fn acknowledgement_control_byte() {
+        assert_eq!(
+            acknowledgement(0x00, PGN::DiagnosticMessage11).pdu()[0],
+            0x00
+        );
+        assert_eq!(
+            negative_acknowledgement(0x00, 0xF9, PGN::DiagnosticMessage11).pdu()[0],
+            0x01
+        );
+
+        for value in 0..4 {
+            let control = AcknowledgementControl::from_value(value).unwrap();
+            assert_eq!(AcknowledgementControl::to_value(control), value);
+        }
+        assert_eq!(AcknowledgementControl::from_value(4), None);
+    }
+
+    #[test]
+    fn acknowledgement_2() {
+        let frame = negative_acknowledgement(0x28, 0xF9, PGN::TimeDate);
+
+        assert_eq!(
+            frame.pdu(),
+            &[0x01, 0xFF, 0xFF, 0xFF, 0xF9, 0xE6, 0xFE, 0x00]
+        );
+
+        let acknowledgement = Acknowledgement::from_frame(&frame).unwrap();
+        assert_eq!(
+            acknowledgement.control,
+            AcknowledgementControl::NotAcknowledged
+        );
+        assert_eq!(acknowledgement.address, 0xF9);
+        assert_eq!(acknowledgement.pgn, PGN::TimeDate);
+
+        let acknowledgement =
+            Acknowledgement::from_pdu(&[0x02, 0x10, 0xFF, 0xFF, 0xF9, 0x00, 0xEF, 0x00]).unwrap();
+        assert_eq!(
+            acknowledgement.control,
+            AcknowledgementControl::AccessDenied
+        );
+        assert_eq!(acknowledgement.group_function, Some(0x10));
+        assert_eq!(acknowledgement.pgn, PGN::ProprietaryA);
+        assert_eq!(
+            acknowledgement.to_pdu(),
+            [0x02, 0x10, 0xFF, 0xFF, 0xF9, 0x00, 0xEF, 0x00]
+        );
+
+        assert!(
+            Acknowledgement::from_pdu(&[0x04, 0xFF, 0xFF, 0xFF, 0xF9, 0x00, 0xEF, 0x00]).is_none()
+        );
+    }
+
+    #[test]
+    fn responder_1() {
+        let mut time_date = |buffer: &mut [u8]| {
+            buffer[..8].copy_from_slice(&[0x3A, 0x01, 0x0A, 0x04, 0x50, 0x27, 0x7D, 0x7D]);
+            Some(8)
+        };
+        let mut software_identification = |buffer: &mut [u8]| {
+            buffer[..20].copy_from_slice(b"1*ABCDEFGHIJKLMNOPQ*");
+            Some(20)
+        };
+
+        let mut buffer = [0; DATA_MAX_LENGTH];
+        let mut responder = Responder::<2>::new(0x28);
+        assert!(responder.register(PGN::TimeDate, &mut time_date).is_ok());
+        assert!(responder
+            .register(PGN::SoftwareIdentification, &mut software_identification)
+            .is_ok());
+        assert!(responder.is_registered(PGN::TimeDate));
+
+        match responder.from_frame(&request(0x28, 0xF9, PGN::TimeDate), &mut buffer) {
+            Some(Reply::Frame(frame)) => {
+                assert_eq!(frame.id().pgn(), PGN::TimeDate);
+                assert_eq!(frame.id().source_address(), 0x28);
+                assert_eq!(frame.pdu()[0], 0x3A);
+            }
+            _ => panic!("expected single frame reply"),
+        }
+
+        match responder.from_frame(
+            &request(0xFF, 0xF9, PGN::SoftwareIdentification),
+            &mut buffer,
+        ) {
+            Some(Reply::Broadcast(mut transmitter)) => {
+                assert_eq!(transmitter.data(), b"1*ABCDEFGHIJKLMNOPQ*");
+                let frame = transmitter.next_frame().unwrap();
+                assert_eq!(frame.id().destination_address(), Some(0xFF));
+            }
+            _ => panic!("expected broadcast reply"),
+        }
+
+        match responder.from_frame(
+            &request(0x28, 0xF9, PGN::SoftwareIdentification),
+            &mut buffer,
+        ) {
+            Some(Reply::Connection(mut transmitter)) => {
+                let frame = transmitter.next_frame().unwrap();
+                assert_eq!(frame.id().pgn(), PGN::TransportProtocolConnectionManagement);
+                assert_eq!(frame.id().destination_address(), Some(0xF9));
+                assert_eq!(frame.pdu()[0], 0x10);
+            }
+            _ => panic!("expected connection reply"),
+        }
+
+        assert!(responder
+            .from_frame(&request(0x29, 0xF9, PGN::TimeDate), &mut buffer)
+            .is_none());
+    }
+
+    #[test]
+    fn responder_2() {
+        let mut unavailable = |_: &mut [u8]| None;
+        let mut other = |_: &mut [u8]| None;
+
+        let mut buffer = [0; DATA_MAX_LENGTH];
+        let mut responder = Responder::<1>::new(0x28);
+        assert!(responder
+            .register(PGN::VehicleIdentification, &mut unavailable)
+            .is_ok());
+        assert!(responder.register(PGN::TimeDate, &mut other).is_err());
+
+        match responder.from_frame(&request(0x28, 0xF9, PGN::TimeDate), &mut buffer) {
+            Some(Reply::Frame(frame)) => {
+                let acknowledgement = Acknowledgement::from_frame(&frame).unwrap();
+                assert_eq!(
+                    acknowledgement.control,
+                    AcknowledgementControl::NotAcknowledged
+                );
+                assert_eq!(acknowledgement.address, 0xF9);
+                assert_eq!(acknowledgement.pgn, PGN::TimeDate);
+            }
+            _ => panic!("expected negative acknowledgement"),
+        }
+
+        match responder.from_frame(
+            &request(0x28, 0xF9, PGN::VehicleIdentification),
+            &mut buffer,
+        ) {
+            Some(Reply::Frame(frame)) => {
+                let acknowledgement = Acknowledgement::from_frame(&frame).unwrap();
+                assert_eq!(
+                    acknowledgement.control,
+                    AcknowledgementControl::CannotRespond
+                );
+            }
+            _ => panic!("expected cannot respond"),
+        }
+
+        assert!(responder
+            .from_frame(&request(0xFF, 0xF9, PGN::TimeDate), &mut buffer)
+            .is_none());
+        assert!(responder
+            .from_frame(
+                &request(0xFF, 0xF9, PGN::VehicleIdentification),
+                &mut buffer
+            )
+            .is_none());
+
+        responder.unregister(PGN::VehicleIdentification);
+        assert!(!responder.is_registered(PGN::VehicleIdentification));
+    }
+
+    #[test]
+    fn responder_3() {
+        let mut time_date = |buffer: &mut [u8]| {
+            buffer[..8].copy_from_slice(&[0x3A, 0x01, 0x0A, 0x04, 0x50, 0x27, 0x7D, 0x7D]);
+            Some(8)
+        };
+        let mut software_identification = |buffer: &mut [u8]| {
+            buffer[..20].copy_from_slice(b"1*ABCDEFGHIJKLMNOPQ*");
+            Some(20)
+        };
+
+        let mut responder = Responder::<2>::new(0x28);
+        assert!(responder.register(PGN::TimeDate, &mut time_date).is_ok());
+        assert!(responder
+            .register(PGN::SoftwareIdentification, &mut software_identification)
+            .is_ok());
+
+        let mut broadcast_buffer = [0; DATA_MAX_LENGTH];
+        let mut transmitter = match responder.from_frame(
+            &request(0xFF, 0xF9, PGN::SoftwareIdentification),
+            &mut broadcast_buffer,
+        ) {
+            Some(Reply::Broadcast(transmitter)) => transmitter,
+            _ => panic!("expected broadcast reply"),
+        };
+
+        assert!(transmitter.next_frame().is_some());
+        assert!(transmitter.next_frame().is_some());
+
+        let mut buffer = [0; DATA_MAX_LENGTH];
+        match responder.from_frame(&request(0x28, 0xF9, PGN::TimeDate), &mut buffer) {
+            Some(Reply::Frame(frame)) => assert_eq!(frame.id().pgn(), PGN::TimeDate),
+            _ => panic!("expected single frame reply"),
+        }
+
+        responder.set_address(0x29);
+        assert_eq!(responder.address(), 0x29);
+
+        assert!(transmitter.next_frame().is_some());
+        assert!(transmitter.next_frame().is_some());
+        assert!(transmitter.is_complete());
+    }
+
+    #[test]
+    fn request2_1() {
+        let request = Request2 {
+            pgn: PGN::SoftwareIdentification,
+            extended_identifier: [0x01, 0xFF, 0xFF],
+            use_transfer: Some(true),
+        };
+
+        let frame = request.to_frame(0xF9, 0x28);
+        assert_eq!(frame.id().pgn(), PGN::Request2);
+        assert_eq!(frame.id().destination_address(), Some(0x28));
+        assert_eq!(
+            frame.pdu(),
+            &[0xDA, 0xFE, 0x00, 0x01, 0xFF, 0xFF, 0xFD, 0xFF]
+        );
+        assert_eq!(Request2::from_pdu(frame.pdu()), Some(request));
+    }
+
+    #[test]
+    fn request2_short() {
+        assert!(Request2::from_pdu(&[0xDA, 0xFE, 0x00, 0x01, 0xFF, 0xFF]).is_none());
+    }
+
+    #[test]
+    fn transfer_1() {
+        let transfer = Transfer {
+            destination_address: 0xF9,
+            pgn: PGN::TimeDate,
+            data: &[0x3A, 0x01, 0x0A, 0x04, 0x50, 0x27, 0x7D, 0x7D],
+        };
+
+        let mut pdu = [0; 16];
+        assert_eq!(transfer.copy_to_slice(&mut pdu), Some(12));
+        assert_eq!(&pdu[..4], &[0xF9, 0xE6, 0xFE, 0x00]);
+        assert_eq!(Transfer::from_pdu(&pdu[..12]), Some(transfer));
+        assert_eq!(transfer.copy_to_slice(&mut pdu[..11]), None);
+        assert_eq!(Transfer::from_pdu(&pdu[..3]), None);
+    }
+
+    #[test]
+    fn requester_1() {
+        let mut requester = Requester::<2>::new(0xF9);
+
+        let frame = requester
+            .request(0x28, PGN::TimeDate, Duration::ZERO)
+            .unwrap();
+        assert_eq!(frame.id().destination_address(), Some(0x28));
+        assert_eq!(request_from_pdu(frame.pdu()), PGN::TimeDate);
+        requester
+            .request(0x28, PGN::SoftwareIdentification, Duration::ZERO)
+            .unwrap();
+        assert!(requester
+            .request(0x29, PGN::TimeDate, Duration::ZERO)
+            .is_none());
+        assert_eq!(requester.len(), 2);
+
+        let id = IdBuilder::from_pgn(PGN::TimeDate).sa(0x29).build();
+        let frame = FrameBuilder::new(id).copy_from_slice(&[0xFF; 8]).build();
+        assert_eq!(requester.from_frame(&frame), None);
+
+        let id = IdBuilder::from_pgn(PGN::TimeDate).sa(0x28).build();
+        let frame = FrameBuilder::new(id).copy_from_slice(&[0xFF; 8]).build();
+        assert_eq!(
+            requester.from_frame(&frame),
+            Some(RequestEvent::Response {
+                address: 0x28,
+                pgn: PGN::TimeDate
+            })
+        );
+        assert!(!requester.is_pending(0x28, PGN::TimeDate));
+
+        let id = IdBuilder::from_pgn(PGN::SoftwareIdentification)
+            .sa(0x28)
+            .da(0xF9)
+            .build();
+        assert_eq!(
+            requester.from_message(&(id, PGN::SoftwareIdentification, &[0x01; 20][..])),
+            Some(RequestEvent::Response {
+                address: 0x28,
+                pgn: PGN::SoftwareIdentification
+            })
+        );
+        assert!(requester.is_empty());
+    }
+
+    #[test]
+    fn requester_2() {
+        let mut requester = Requester::<2>::new(0xF9);
+
+        requester
+            .request(0x28, PGN::TimeDate, Duration::from_millis(100))
+            .unwrap();
+        requester
+            .request(0x29, PGN::VehicleIdentification, Duration::from_millis(100))
+            .unwrap();
+
+        let frame = negative_acknowledgement(0x28, 0xF9, PGN::TimeDate);
+        assert_eq!(
+            requester.from_frame(&frame),
+            Some(RequestEvent::Acknowledged {
+                address: 0x28,
+                acknowledgement: Acknowledgement {
+                    control: AcknowledgementControl::NotAcknowledged,
+                    group_function: None,
+                    address: 0xF9,
+                    pgn: PGN::TimeDate,
+                }
+            })
+        );
+
+        assert_eq!(requester.tick(Duration::from_millis(1349)), None);
+        assert_eq!(
+            requester.tick(Duration::from_millis(1350)),
+            Some(RequestEvent::Timeout {
+                da: 0x29,
+                pgn: PGN::VehicleIdentification
+            })
+        );
+        assert!(requester.is_empty());
+    }
+
+    #[test]
+    fn requester_3() {
+        let mut requester = Requester::<1>::new(0xF9);
+
+        requester
+            .request(0xFF, PGN::SoftwareIdentification, Duration::ZERO)
+            .unwrap();
+
+        for address in [0x28, 0x29] {
+            let id = IdBuilder::from_pgn(PGN::SoftwareIdentification)
+                .sa(address)
+                .build();
+            assert_eq!(
+                requester.from_message(&(id, PGN::SoftwareIdentification, &[0x01; 20][..])),
+                Some(RequestEvent::Response {
+                    address,
+                    pgn: PGN::SoftwareIdentification
+                })
+            );
+        }
+
+        assert!(requester.is_pending(0xFF, PGN::SoftwareIdentification));
+        assert_eq!(requester.tick(Duration::from_millis(1250)), None);
+        assert!(requester.is_empty());
+    }
+
+    #[test]
+    fn requester_4() {
+        let mut requester = Requester::<2>::new(0xF9);
+
+        requester
+            .request(0xFF, PGN::TimeDate, Duration::ZERO)
+            .unwrap();
+        requester
+            .request(0x28, PGN::TimeDate, Duration::ZERO)
+            .unwrap();
+
+        let id = IdBuilder::from_pgn(PGN::TimeDate).sa(0x28).build();
+        let frame = FrameBuilder::new(id).copy_from_slice(&[0xFF; 8]).build();
+        assert_eq!(
+            requester.from_frame(&frame),
+            Some(RequestEvent::Response {
+                address: 0x28,
+                pgn: PGN::TimeDate
+            })
+        );
+        assert!(!requester.is_pending(0x28, PGN::TimeDate));
+        assert!(requester.is_pending(0xFF, PGN::TimeDate));
+
+        assert_eq!(
+            requester.tick(Duration::from_millis(1250)),
+            Some(RequestEvent::Timeout {
+                da: 0xFF,
+                pgn: PGN::TimeDate
+            })
+        );
+        assert!(requester.is_empty());
+    }
+}
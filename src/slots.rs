@@ -14,6 +14,14 @@ pub fn bool_to_value(value: Option<bool>) -> u8 {
     }
 }
 
+pub fn bit_from_value(value: u8) -> bool {
+    value & 0b1 == 0b1
+}
+
+pub fn bit_to_value(value: bool) -> u8 {
+    value as u8
+}
+
 struct Param {
     scale: f32,
     offset: f32,
@@ -75,6 +83,29 @@ pub mod count {
     }
 }
 
+pub mod count2 {
+    const RESOLUTION: super::Param = super::Param {
+        scale: 1.0,
+        offset: 0.0,
+        limit_lower: 0.0,
+        limit_upper: 64255.0,
+    };
+
+    pub fn dec(value: [u8; 2]) -> Option<u16> {
+        if value == [crate::PDU_NOT_AVAILABLE; 2] {
+            return None;
+        }
+
+        Some(RESOLUTION.dec(u16::from_le_bytes(value) as f32) as u16)
+    }
+
+    pub fn enc(value: Option<u16>) -> [u8; 2] {
+        value.map_or([crate::PDU_NOT_AVAILABLE; 2], |v| {
+            (RESOLUTION.enc(v as f32) as u16).to_le_bytes()
+        })
+    }
+}
+
 pub mod rotational_velocity {
     const RESOLUTION: super::Param = super::Param {
         scale: 0.125,
@@ -142,6 +173,27 @@ pub mod temperature2 {
     }
 }
 
+pub mod temperature3 {
+    const RESOLUTION: super::Param = super::Param {
+        scale: 1.0,
+        offset: -40.0,
+        limit_lower: -40.0,
+        limit_upper: 210.0,
+    };
+
+    pub fn dec(value: u8) -> Option<i16> {
+        if value == crate::PDU_NOT_AVAILABLE {
+            return None;
+        }
+
+        Some(RESOLUTION.dec(value as f32) as i16)
+    }
+
+    pub fn enc(value: Option<i16>) -> u8 {
+        value.map_or(crate::PDU_NOT_AVAILABLE, |v| RESOLUTION.enc(v as f32) as u8)
+    }
+}
+
 pub mod electrical_current {
     const RESOLUTION: super::Param = super::Param {
         scale: 1.0,
@@ -382,6 +434,27 @@ pub mod pressure5 {
     }
 }
 
+pub mod pressure6 {
+    const RESOLUTION: super::Param = super::Param {
+        scale: 2.0,
+        offset: 0.0,
+        limit_lower: 0.0,
+        limit_upper: 500.0,
+    };
+
+    pub fn dec(value: u8) -> Option<u16> {
+        if value == crate::PDU_NOT_AVAILABLE {
+            return None;
+        }
+
+        Some(RESOLUTION.dec(value as f32) as u16)
+    }
+
+    pub fn enc(value: Option<u16>) -> u8 {
+        value.map_or(crate::PDU_NOT_AVAILABLE, |v| RESOLUTION.enc(v as f32) as u8)
+    }
+}
+
 pub mod liquid_fuel_usage {
     const RESOLUTION: super::Param = super::Param {
         scale: 0.5,
@@ -451,6 +524,29 @@ pub mod time {
     }
 }
 
+pub mod speed {
+    const RESOLUTION: super::Param = super::Param {
+        scale: 1.0 / 256.0,
+        offset: 0.0,
+        limit_lower: 0.0,
+        limit_upper: 250.996,
+    };
+
+    pub fn dec(value: [u8; 2]) -> Option<u8> {
+        if value == [crate::PDU_NOT_AVAILABLE; 2] {
+            return None;
+        }
+
+        Some(RESOLUTION.dec(u16::from_le_bytes(value) as f32) as u8)
+    }
+
+    pub fn enc(value: Option<u8>) -> [u8; 2] {
+        value.map_or([crate::PDU_NOT_AVAILABLE; 2], |v| {
+            (RESOLUTION.enc(v as f32) as u16).to_le_bytes()
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -463,6 +559,14 @@ mod tests {
         assert_eq!(decoded, Some(94));
     }
 
+    #[test]
+    fn count_test_2() {
+        let value = Some(1200);
+        let encoded = count2::enc(value);
+        let decoded = count2::dec(encoded);
+        assert_eq!(decoded, Some(1200));
+    }
+
     #[test]
     fn rotational_velocity_test_1() {
         let value = Some(900);
@@ -487,6 +591,14 @@ mod tests {
         assert_eq!(decoded, Some(-13));
     }
 
+    #[test]
+    fn temperature_test_3() {
+        let value = Some(180);
+        let encoded = temperature3::enc(value);
+        let decoded = temperature3::dec(encoded);
+        assert_eq!(decoded, Some(180));
+    }
+
     #[test]
     fn position_level_test_1() {
         let value = Some(50);
@@ -535,6 +647,14 @@ mod tests {
         assert_eq!(decoded, Some(-178));
     }
 
+    #[test]
+    fn pressure_test_6() {
+        let value = Some(340);
+        let encoded = pressure6::enc(value);
+        let decoded = pressure6::dec(encoded);
+        assert_eq!(decoded, Some(340));
+    }
+
     #[test]
     fn liquid_fuel_usage_test_1() {
         let value = Some(7863247);
@@ -1,10 +1,36 @@
-use crate::{Frame, FrameBuilder, IdBuilder, PDU_NOT_AVAILABLE, PGN};
+use core::time::Duration;
+
+use crate::{Frame, FrameBuilder, Id, IdBuilder, PDU_MAX_LENGTH, PDU_NOT_AVAILABLE, PGN};
 
 /// Maximum number of data bytes
 pub const DATA_MAX_LENGTH: usize = 1785;
 /// Maximum number of data bytes per frame
 pub const DATA_FRAME_SIZE: usize = 7;
 
+/// Maximum number of data bytes using the extended transport protocol
+pub const EXTENDED_DATA_MAX_LENGTH: usize = 117_440_505;
+
+/// Maximum number of data bytes using the NMEA 2000 fast packet protocol
+pub const FAST_PACKET_DATA_MAX_LENGTH: usize = 223;
+
+/// Maximum number of packets in a single transport session
+pub const PACKET_MAX_COUNT: usize = 255;
+
+/// Minimum time between broadcast data packets
+pub const BROADCAST_PACKET_INTERVAL: Duration = Duration::from_millis(50);
+/// Hold time (Th)
+pub const TIMEOUT_TH: Duration = Duration::from_millis(500);
+/// Time between data packets on the receiving side (T1)
+pub const TIMEOUT_T1: Duration = Duration::from_millis(750);
+/// Time between a clear to send and the first data packet on the receiving side (T2)
+pub const TIMEOUT_T2: Duration = Duration::from_millis(1250);
+/// Time between the last data packet and the next clear to send or end of message
+/// acknowledgment on the transmitting side (T3)
+pub const TIMEOUT_T3: Duration = Duration::from_millis(1250);
+/// Time between a hold clear to send and the next clear to send on the transmitting side (T4)
+pub const TIMEOUT_T4: Duration = Duration::from_millis(1050);
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum ConnectionManagement {
     RequestToSend = 0x10,
     ClearToSend = 0x11,
@@ -13,9 +39,314 @@ pub enum ConnectionManagement {
     Abort = 0xff,
 }
 
+impl ConnectionManagement {
+    pub fn from_value(value: u8) -> Option<Self> {
+        match value {
+            0x10 => Some(Self::RequestToSend),
+            0x11 => Some(Self::ClearToSend),
+            0x13 => Some(Self::EndOfMessageAcknowledgment),
+            0x20 => Some(Self::BroadcastAnnounceMessage),
+            0xff => Some(Self::Abort),
+            _ => None,
+        }
+    }
+}
+
+/// Connection abort reason.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AbortReason {
+    /// Already in one or more connection managed sessions and cannot support another.
+    AlreadyInSession,
+    /// System resources were needed for another task so this connection managed session was terminated.
+    SystemResources,
+    /// A timeout occurred and this is the connection abort to close the session.
+    Timeout,
+    /// CTS messages received when data transfer is in progress.
+    ClearToSendInProgress,
+    /// Maximum retransmit request limit reached.
+    MaximumRetransmit,
+    /// Unexpected data transfer packet.
+    UnexpectedDataTransfer,
+    /// Bad sequence number.
+    BadSequence,
+    /// Duplicate sequence number.
+    DuplicateSequence,
+    /// Unexpected EDPO packet.
+    UnexpectedDataPacketOffset,
+    /// Unexpected EDPO PGN.
+    UnexpectedDataPacketOffsetPGN,
+    /// EDPO number of packets is greater than CTS.
+    DataPacketOffsetExceedsClearToSend,
+    /// Bad EDPO offset.
+    BadDataPacketOffset,
+    /// Unexpected ECTS PGN.
+    UnexpectedClearToSendPGN,
+    /// ECTS requested packets exceeds message size.
+    ClearToSendExceedsMessageSize,
+    /// Any other reason.
+    Other,
+}
+
+impl AbortReason {
+    pub fn from_value(value: u8) -> Self {
+        match value {
+            1 => AbortReason::AlreadyInSession,
+            2 => AbortReason::SystemResources,
+            3 => AbortReason::Timeout,
+            4 => AbortReason::ClearToSendInProgress,
+            5 => AbortReason::MaximumRetransmit,
+            6 => AbortReason::UnexpectedDataTransfer,
+            7 => AbortReason::BadSequence,
+            8 => AbortReason::DuplicateSequence,
+            9 => AbortReason::UnexpectedDataPacketOffset,
+            10 => AbortReason::UnexpectedDataPacketOffsetPGN,
+            11 => AbortReason::DataPacketOffsetExceedsClearToSend,
+            12 => AbortReason::BadDataPacketOffset,
+            14 => AbortReason::UnexpectedClearToSendPGN,
+            15 => AbortReason::ClearToSendExceedsMessageSize,
+            _ => AbortReason::Other,
+        }
+    }
+
+    pub fn to_value(reason: Self) -> u8 {
+        match reason {
+            AbortReason::AlreadyInSession => 1,
+            AbortReason::SystemResources => 2,
+            AbortReason::Timeout => 3,
+            AbortReason::ClearToSendInProgress => 4,
+            AbortReason::MaximumRetransmit => 5,
+            AbortReason::UnexpectedDataTransfer => 6,
+            AbortReason::BadSequence => 7,
+            AbortReason::DuplicateSequence => 8,
+            AbortReason::UnexpectedDataPacketOffset => 9,
+            AbortReason::UnexpectedDataPacketOffsetPGN => 10,
+            AbortReason::DataPacketOffsetExceedsClearToSend => 11,
+            AbortReason::BadDataPacketOffset => 12,
+            AbortReason::UnexpectedClearToSendPGN => 14,
+            AbortReason::ClearToSendExceedsMessageSize => 15,
+            AbortReason::Other => 250,
+        }
+    }
+}
+
+/// Transport error.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TransportError {
+    /// Already in a connection managed session with another node.
+    AlreadyInSession,
+    /// Not enough resources to accept the session.
+    InsufficientResources,
+    /// A timeout occurred.
+    Timeout,
+    /// Clear to send received while data transfer is in progress.
+    ClearToSendInProgress,
+    /// Data transfer packet received outside of a session.
+    UnexpectedDataTransfer,
+    /// Data transfer packet with an out of order or out of range sequence number.
+    BadSequence,
+    /// Data transfer packet with the sequence number of the previous packet.
+    DuplicateSequence,
+    /// Data packet offset received outside of a session.
+    UnexpectedDataPacketOffset,
+    /// Data packet offset outside of the message.
+    BadDataPacketOffset,
+    /// Message size out of range or inconsistent with the number of packets.
+    BadLength,
+    /// Frame is not a valid transport protocol frame.
+    InvalidFrame,
+    /// Session was aborted by the other node.
+    Aborted(AbortReason),
+}
+
+impl TransportError {
+    /// Returns the abort reason to close the session with.
+    pub fn abort_reason(&self) -> AbortReason {
+        match self {
+            TransportError::AlreadyInSession => AbortReason::AlreadyInSession,
+            TransportError::InsufficientResources => AbortReason::SystemResources,
+            TransportError::Timeout => AbortReason::Timeout,
+            TransportError::ClearToSendInProgress => AbortReason::ClearToSendInProgress,
+            TransportError::UnexpectedDataTransfer => AbortReason::UnexpectedDataTransfer,
+            TransportError::BadSequence => AbortReason::BadSequence,
+            TransportError::DuplicateSequence => AbortReason::DuplicateSequence,
+            TransportError::UnexpectedDataPacketOffset => AbortReason::UnexpectedDataPacketOffset,
+            TransportError::BadDataPacketOffset => AbortReason::BadDataPacketOffset,
+            TransportError::BadLength | TransportError::InvalidFrame => AbortReason::Other,
+            TransportError::Aborted(reason) => *reason,
+        }
+    }
+}
+
+impl core::fmt::Display for TransportError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            TransportError::AlreadyInSession => write!(f, "Already in session"),
+            TransportError::InsufficientResources => write!(f, "Insufficient resources"),
+            TransportError::Timeout => write!(f, "Timeout"),
+            TransportError::ClearToSendInProgress => {
+                write!(f, "Clear to send while data transfer is in progress")
+            }
+            TransportError::UnexpectedDataTransfer => write!(f, "Unexpected data transfer"),
+            TransportError::BadSequence => write!(f, "Bad sequence number"),
+            TransportError::DuplicateSequence => write!(f, "Duplicate sequence number"),
+            TransportError::UnexpectedDataPacketOffset => {
+                write!(f, "Unexpected data packet offset")
+            }
+            TransportError::BadDataPacketOffset => write!(f, "Bad data packet offset"),
+            TransportError::BadLength => write!(f, "Bad message length"),
+            TransportError::InvalidFrame => write!(f, "Invalid frame"),
+            TransportError::Aborted(reason) => write!(f, "Aborted: {:?}", reason),
+        }
+    }
+}
+
+/// Connection abort (TP.CM_Abort).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ConnectionAbort {
+    /// Reason for the abort.
+    pub reason: AbortReason,
+    /// PGN of the aborted message.
+    pub pgn: PGN,
+}
+
+impl ConnectionAbort {
+    pub fn from_pdu(pdu: &[u8]) -> Result<Self, TransportError> {
+        if pdu.len() != PDU_MAX_LENGTH || pdu[0] != ConnectionManagement::Abort as u8 {
+            return Err(TransportError::InvalidFrame);
+        }
+
+        Ok(Self {
+            reason: AbortReason::from_value(pdu[1]),
+            pgn: PGN::from_le_bytes([pdu[5], pdu[6], pdu[7]]),
+        })
+    }
+
+    pub fn to_pdu(&self) -> [u8; 8] {
+        let pgn_bytes = self.pgn.to_le_bytes();
+
+        [
+            ConnectionManagement::Abort as u8,
+            AbortReason::to_value(self.reason),
+            PDU_NOT_AVAILABLE,
+            PDU_NOT_AVAILABLE,
+            PDU_NOT_AVAILABLE,
+            pgn_bytes[0],
+            pgn_bytes[1],
+            pgn_bytes[2],
+        ]
+    }
+
+    /// Create the abort frame from the source address to the destination address.
+    pub fn to_frame(&self, sa: u8, da: u8) -> Frame {
+        connection_management_frame(sa, da, &self.to_pdu())
+    }
+}
+
+/// Session timer driven by the caller supplied clock.
+///
+/// The timer starts at the time it is armed if that time is known, otherwise on the first
+/// tick after it was armed.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct Timer {
+    start: Option<Duration>,
+    timeout: Option<Duration>,
+}
+
+impl Timer {
+    pub(crate) const fn new() -> Self {
+        Self {
+            start: None,
+            timeout: None,
+        }
+    }
+
+    /// Arm the timer on the next tick.
+    pub(crate) fn arm(&mut self, timeout: Duration) {
+        self.start = None;
+        self.timeout = Some(timeout);
+    }
+
+    /// Arm the timer from a known point in time.
+    pub(crate) fn arm_at(&mut self, now: Duration, timeout: Duration) {
+        self.start = Some(now);
+        self.timeout = Some(timeout);
+    }
+
+    /// Start an armed timer at a known point in time, unless it already started.
+    pub(crate) fn start(&mut self, now: Duration) {
+        if self.timeout.is_some() && self.start.is_none() {
+            self.start = Some(now);
+        }
+    }
+
+    /// Returns `true` if the timer is armed.
+    pub(crate) fn is_armed(&self) -> bool {
+        self.timeout.is_some()
+    }
+
+    /// Disarm the timer.
+    pub(crate) fn disarm(&mut self) {
+        self.timeout = None;
+    }
+
+    /// Returns `true` if the timer is armed and expired.
+    pub(crate) fn is_expired(&mut self, now: Duration) -> bool {
+        match self.timeout {
+            Some(timeout) => now.saturating_sub(*self.start.get_or_insert(now)) >= timeout,
+            None => false,
+        }
+    }
+}
+
+/// Calculate the number of packets required to transfer the data.
+fn packet_count(data_length: usize) -> usize {
+    let quotient = data_length / DATA_FRAME_SIZE;
+    let remainder = data_length % DATA_FRAME_SIZE;
+
+    if remainder > 0 {
+        quotient + 1
+    } else {
+        quotient
+    }
+}
+
+/// Create a connection management frame.
+fn connection_management_frame(sa: u8, da: u8, pdu: &[u8]) -> Frame {
+    FrameBuilder::new(
+        IdBuilder::from_pgn(PGN::TransportProtocolConnectionManagement)
+            .priority(7)
+            .sa(sa)
+            .da(da)
+            .build(),
+    )
+    .copy_from_slice(pdu)
+    .build()
+}
+
+/// Create a data transfer frame.
+///
+/// The packet is padded with `PDU_NOT_AVAILABLE` if the data chunk is shorter than the frame size.
+fn data_transfer_frame(sa: u8, da: u8, sequence: u8, data_chunk: &[u8]) -> Frame {
+    let mut frame_builder = FrameBuilder::new(
+        IdBuilder::from_pgn(PGN::TransportProtocolDataTransfer)
+            .priority(7)
+            .sa(sa)
+            .da(da)
+            .build(),
+    );
+
+    let payload = frame_builder.as_mut();
+    payload[0] = sequence;
+    payload[1..(data_chunk.len() + 1)].copy_from_slice(data_chunk);
+
+    frame_builder.set_len(8).build()
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum BroadcastTransportState {
     ConnectionManagement,
     DataTransfer(u8),
+    Aborted(AbortReason),
 }
 
 pub struct BroadcastTransport {
@@ -25,6 +356,8 @@ pub struct BroadcastTransport {
     data_length: usize,
     tail: usize,
     state: BroadcastTransportState,
+    transmit: bool,
+    timer: Timer,
 }
 
 impl BroadcastTransport {
@@ -36,14 +369,24 @@ impl BroadcastTransport {
             data_length: 0,
             tail: 0,
             state: BroadcastTransportState::ConnectionManagement,
+            transmit: false,
+            timer: Timer::new(),
         }
     }
 
-    pub fn with_data(mut self, data: &[u8]) -> Self {
+    /// Set the message data to transmit.
+    ///
+    /// Fails with `TransportError::BadLength` if the data is longer than `DATA_MAX_LENGTH` bytes.
+    pub fn with_data(mut self, data: &[u8]) -> Result<Self, TransportError> {
+        if data.len() > DATA_MAX_LENGTH {
+            return Err(TransportError::BadLength);
+        }
+
         self.data[..data.len()].copy_from_slice(data);
         self.data_length = data.len();
         self.tail = data.len();
-        self
+        self.transmit = true;
+        Ok(self)
     }
 
     /// Returns a slice of the transport data.
@@ -63,19 +406,36 @@ impl BroadcastTransport {
         self.tail == 0
     }
 
-    pub fn packet_count(&self) -> usize {
-        let quotient = self.data_length / DATA_FRAME_SIZE;
-        let remainder = self.data_length % DATA_FRAME_SIZE;
+    /// Returns the PGN of the transported message.
+    #[inline]
+    pub fn pgn(&self) -> PGN {
+        self.pgn
+    }
 
-        if remainder > 0 {
-            quotient + 1
-        } else {
-            quotient
+    /// Returns the current state of the session.
+    #[inline]
+    pub fn state(&self) -> BroadcastTransportState {
+        self.state
+    }
+
+    /// Returns `true` if all packets are transferred.
+    pub fn is_complete(&self) -> bool {
+        match self.state {
+            BroadcastTransportState::DataTransfer(packet) => packet as usize >= self.packet_count(),
+            _ => false,
         }
     }
 
-    pub fn next_frame(&mut self) -> Frame {
+    pub fn packet_count(&self) -> usize {
+        packet_count(self.data_length)
+    }
+
+    /// Returns the next frame to send, if any.
+    ///
+    /// No frame is returned once all packets are sent, or the session was aborted.
+    pub fn next_frame(&mut self) -> Option<Frame> {
         match self.state {
+            BroadcastTransportState::Aborted(_) => None,
             BroadcastTransportState::ConnectionManagement => {
                 let data_length = (self.data_length as u16).to_le_bytes();
                 let packets = self.packet_count() as u8;
@@ -102,10 +462,13 @@ impl BroadcastTransport {
 
                 self.state = BroadcastTransportState::DataTransfer(0);
 
-                frame
+                Some(frame)
             }
-            // TODO: Return error frame if packet is out of bounds.
             BroadcastTransportState::DataTransfer(packet) => {
+                if packet as usize >= self.packet_count() {
+                    return None;
+                }
+
                 let sequence = packet + 1;
 
                 let mut frame_builder = FrameBuilder::new(
@@ -132,33 +495,100 @@ impl BroadcastTransport {
 
                 self.state = BroadcastTransportState::DataTransfer(packet + 1);
 
-                frame
+                Some(frame)
+            }
+        }
+    }
+
+    /// Advance the session to the current time.
+    ///
+    /// On the transmitting side the next frame is returned once the broadcast packet interval
+    /// has elapsed since the previous frame. On the receiving side the session is aborted with
+    /// a timeout if no data packet arrived within T1. Broadcast sessions are never aborted on
+    /// the bus, so no abort frame is returned.
+    pub fn tick(&mut self, now: Duration) -> Option<Frame> {
+        if !self.transmit {
+            if self.timer.is_expired(now) {
+                self.timer.disarm();
+                self.state = BroadcastTransportState::Aborted(AbortReason::Timeout);
             }
+            return None;
+        }
+
+        if self.is_complete() || matches!(self.state, BroadcastTransportState::Aborted(_)) {
+            return None;
+        }
+
+        if self.timer.is_armed() && !self.timer.is_expired(now) {
+            return None;
         }
+
+        self.timer.arm_at(now, BROADCAST_PACKET_INTERVAL);
+
+        self.next_frame()
     }
 
-    pub fn from_frame(&mut self, frame: &Frame) {
+    /// Process a broadcast frame.
+    ///
+    /// On error the session is left unchanged.
+    pub fn from_frame(&mut self, frame: &Frame) -> Result<(), TransportError> {
         let pgn = frame.id().pgn();
         if pgn == PGN::TransportProtocolConnectionManagement {
             let data = frame.as_ref();
-            let data_length = u16::from_le_bytes([data[1], data[2]]) as usize;
+            if data.len() != PDU_MAX_LENGTH {
+                return Err(TransportError::InvalidFrame);
+            }
 
             if data[0] == ConnectionManagement::BroadcastAnnounceMessage as u8 {
+                let data_length = u16::from_le_bytes([data[1], data[2]]) as usize;
+                if data_length == 0
+                    || data_length > DATA_MAX_LENGTH
+                    || data[3] as usize != packet_count(data_length)
+                {
+                    return Err(TransportError::BadLength);
+                }
+
                 self.pgn = PGN::from_le_bytes([data[5], data[6], data[7]]);
                 self.data_length = data_length;
+                self.tail = 0;
                 self.state = BroadcastTransportState::DataTransfer(0);
+                self.timer.arm(TIMEOUT_T1);
             }
         } else if pgn == PGN::TransportProtocolDataTransfer {
             let data = frame.as_ref();
+            if data.len() != PDU_MAX_LENGTH {
+                return Err(TransportError::InvalidFrame);
+            }
+
+            let packet = match self.state {
+                BroadcastTransportState::DataTransfer(packet) => packet,
+                _ => return Err(TransportError::UnexpectedDataTransfer),
+            };
+
             let sequence = data[0];
-            let data_chunk = &data[1..];
+            if sequence > 0 && sequence == packet {
+                return Err(TransportError::DuplicateSequence);
+            } else if sequence as usize != packet as usize + 1
+                || sequence as usize > self.packet_count()
+            {
+                return Err(TransportError::BadSequence);
+            }
 
             let start = (sequence as usize - 1) * DATA_FRAME_SIZE;
-            let end = start + data_chunk.len();
+            let end = (start + DATA_FRAME_SIZE).min(self.data_length);
 
-            self.tail = self.data_length.min(end);
-            self.data[start..end].copy_from_slice(data_chunk);
+            self.data[start..end].copy_from_slice(&data[1..(end - start + 1)]);
+            self.tail = end;
+            self.state = BroadcastTransportState::DataTransfer(sequence);
+
+            if self.is_complete() {
+                self.timer.disarm();
+            } else {
+                self.timer.arm(TIMEOUT_T1);
+            }
         }
+
+        Ok(())
     }
 }
 
@@ -168,73 +598,3156 @@ impl AsRef<[u8]> for BroadcastTransport {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::Id;
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BroadcastTransmitterState {
+    /// Broadcast announce message is pending.
+    ConnectionManagement,
+    /// Number of data packets sent.
+    DataTransfer(u8),
+}
 
-    use super::*;
+/// Broadcast announce message (BAM) transmitter.
+///
+/// Unlike `BroadcastTransport` the transmitter does not copy the message data into the
+/// session. The data is borrowed and every data packet is produced from the data as it is
+/// sent, so a session only takes a few bytes regardless of the message size.
+pub struct BroadcastTransmitter<'a> {
+    sa: u8,
+    pgn: PGN,
+    data: &'a [u8],
+    state: BroadcastTransmitterState,
+    timer: Timer,
+}
 
-    #[test]
-    fn test_broadcast_transport() {
-        let data = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09];
+impl<'a> BroadcastTransmitter<'a> {
+    pub fn new(sa: u8, pgn: PGN) -> Self {
+        Self {
+            sa,
+            pgn,
+            data: &[],
+            state: BroadcastTransmitterState::ConnectionManagement,
+            timer: Timer::new(),
+        }
+    }
 
-        let mut transport = BroadcastTransport::new(0x01, PGN::AddressClaimed).with_data(&data);
+    /// Set the message data.
+    ///
+    /// Fails with `TransportError::BadLength` if the data is longer than `DATA_MAX_LENGTH` bytes.
+    pub fn with_data(mut self, data: &'a [u8]) -> Result<Self, TransportError> {
+        if data.len() > DATA_MAX_LENGTH {
+            return Err(TransportError::BadLength);
+        }
 
-        let frame = transport.next_frame();
-        assert_eq!(frame.id().as_raw(), 0x1CECFF01);
-        assert_eq!(frame.len(), 8);
-        assert_eq!(
-            frame.as_ref(),
-            &[0x20, 0x09, 0x00, 0x02, 0xFF, 0x00, 0xEE, 0x00]
-        );
+        self.data = data;
+        Ok(self)
+    }
 
-        let frame = transport.next_frame();
-        assert_eq!(frame.id().as_raw(), 0x1CEBFF01);
-        assert_eq!(frame.len(), 8);
-        assert_eq!(
-            frame.as_ref(),
-            &[0x01, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07]
-        );
+    /// Returns a slice of the transport data.
+    pub fn data(&self) -> &[u8] {
+        self.data
+    }
 
-        let frame = transport.next_frame();
-        assert_eq!(frame.id().as_raw(), 0x1CEBFF01);
-        assert_eq!(frame.len(), 8);
-        assert_eq!(
-            frame.as_ref(),
-            &[0x02, 0x08, 0x09, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]
-        );
+    /// Returns the length of the transport data.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len()
     }
 
-    #[test]
-    fn test_broadcast_transport2() {
-        let frame1 = [0x20, 0x09, 0x00, 0x02, 0xFF, 0x00, 0xEE, 0x00];
-        let frame2 = [0x01, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07];
-        let frame3 = [0x02, 0x08, 0x09, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
+    /// Returns `true` if the transport data is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
 
-        let mut transport = BroadcastTransport::new(0x01, PGN::AddressClaimed);
+    /// Returns the PGN of the transported message.
+    #[inline]
+    pub fn pgn(&self) -> PGN {
+        self.pgn
+    }
 
-        transport.from_frame(
-            &FrameBuilder::new(Id::new(0x1CECFF01))
-                .copy_from_slice(&frame1)
-                .build(),
-        );
-        assert_eq!(transport.len(), 0);
-        assert_eq!(transport.packet_count(), 2);
+    /// Returns the current state of the session.
+    #[inline]
+    pub fn state(&self) -> BroadcastTransmitterState {
+        self.state
+    }
 
-        transport.from_frame(
-            &FrameBuilder::new(Id::new(0x1CEBFF01))
-                .copy_from_slice(&frame2)
-                .build(),
-        );
-        transport.from_frame(
-            &FrameBuilder::new(Id::new(0x1CEBFF01))
-                .copy_from_slice(&frame3)
-                .build(),
-        );
-        assert_eq!(transport.len(), 9);
-        assert_eq!(
-            transport.data(),
-            &[0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09]
-        );
+    /// Returns `true` if all packets are sent.
+    pub fn is_complete(&self) -> bool {
+        match self.state {
+            BroadcastTransmitterState::DataTransfer(packet) => {
+                packet as usize >= self.packet_count()
+            }
+            _ => false,
+        }
+    }
+
+    pub fn packet_count(&self) -> usize {
+        packet_count(self.data.len())
+    }
+
+    /// Returns the next frame to send, if any.
+    ///
+    /// No frame is returned once all packets are sent.
+    pub fn next_frame(&mut self) -> Option<Frame> {
+        match self.state {
+            BroadcastTransmitterState::ConnectionManagement => {
+                let data_length = (self.data.len() as u16).to_le_bytes();
+                let pgn_bytes = self.pgn.to_le_bytes();
+
+                self.state = BroadcastTransmitterState::DataTransfer(0);
+
+                Some(connection_management_frame(
+                    self.sa,
+                    0xff,
+                    &[
+                        ConnectionManagement::BroadcastAnnounceMessage as u8,
+                        data_length[0],
+                        data_length[1],
+                        self.packet_count() as u8,
+                        PDU_NOT_AVAILABLE,
+                        pgn_bytes[0],
+                        pgn_bytes[1],
+                        pgn_bytes[2],
+                    ],
+                ))
+            }
+            BroadcastTransmitterState::DataTransfer(packet) => {
+                if packet as usize >= self.packet_count() {
+                    return None;
+                }
+
+                let start = packet as usize * DATA_FRAME_SIZE;
+                let end = (start + DATA_FRAME_SIZE).min(self.data.len());
+
+                self.state = BroadcastTransmitterState::DataTransfer(packet + 1);
+
+                Some(data_transfer_frame(
+                    self.sa,
+                    0xff,
+                    packet + 1,
+                    &self.data[start..end],
+                ))
+            }
+        }
+    }
+
+    /// Advance the session to the current time.
+    ///
+    /// The next frame is returned once the broadcast packet interval has elapsed since the
+    /// previous frame.
+    pub fn tick(&mut self, now: Duration) -> Option<Frame> {
+        if self.is_complete() {
+            return None;
+        }
+
+        if self.timer.is_armed() && !self.timer.is_expired(now) {
+            return None;
+        }
+
+        self.timer.arm_at(now, BROADCAST_PACKET_INTERVAL);
+
+        self.next_frame()
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ConnectionTransmitterState {
+    /// Request to send is pending.
+    RequestToSend,
+    /// Waiting for the receiver to clear the next window.
+    ClearToSend,
+    /// Sending the packets in the current window.
+    DataTransfer {
+        /// Next sequence number to send.
+        sequence: u8,
+        /// Last sequence number in the window.
+        last: u8,
+    },
+    /// Waiting for the end of message acknowledgment.
+    EndOfMessageAcknowledgment,
+    /// Receiver acknowledged the message.
+    Complete,
+    /// Session was aborted.
+    Aborted(AbortReason),
+}
+
+/// Connection mode data transfer transmitter.
+///
+/// The transmitter sends a request to send (RTS) to the destination address and waits for
+/// the receiver to clear (CTS) a window of packets. Once all packets are sent the receiver
+/// acknowledges the message with an end of message acknowledgment (EoMA).
+///
+/// The transmitter borrows the message data, frames are produced from the data as they are
+/// sent.
+pub struct ConnectionTransmitter<'a> {
+    sa: u8,
+    da: u8,
+    pgn: PGN,
+    data: &'a [u8],
+    state: ConnectionTransmitterState,
+    timer: Timer,
+}
+
+impl<'a> ConnectionTransmitter<'a> {
+    pub fn new(sa: u8, da: u8, pgn: PGN) -> Self {
+        Self {
+            sa,
+            da,
+            pgn,
+            data: &[],
+            state: ConnectionTransmitterState::RequestToSend,
+            timer: Timer::new(),
+        }
+    }
+
+    /// Set the message data.
+    ///
+    /// Fails with `TransportError::BadLength` if the data is longer than `DATA_MAX_LENGTH` bytes.
+    pub fn with_data(mut self, data: &'a [u8]) -> Result<Self, TransportError> {
+        if data.len() > DATA_MAX_LENGTH {
+            return Err(TransportError::BadLength);
+        }
+
+        self.data = data;
+        Ok(self)
+    }
+
+    /// Returns a slice of the transport data.
+    pub fn data(&self) -> &[u8] {
+        self.data
+    }
+
+    /// Returns the length of the transport data.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns `true` if the transport data is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns the current state of the session.
+    #[inline]
+    pub fn state(&self) -> ConnectionTransmitterState {
+        self.state
+    }
+
+    /// Returns `true` if the receiver acknowledged the message.
+    #[inline]
+    pub fn is_complete(&self) -> bool {
+        self.state == ConnectionTransmitterState::Complete
+    }
+
+    pub fn packet_count(&self) -> usize {
+        packet_count(self.data.len())
+    }
+
+    /// Abort the session.
+    ///
+    /// Returns the abort frame to send to the receiver.
+    pub fn abort(&mut self, reason: AbortReason) -> Frame {
+        self.state = ConnectionTransmitterState::Aborted(reason);
+        self.timer.disarm();
+
+        ConnectionAbort {
+            reason,
+            pgn: self.pgn,
+        }
+        .to_frame(self.sa, self.da)
+    }
+
+    /// Returns the next frame to send, if any.
+    ///
+    /// No frame is returned while the transmitter is waiting for the receiver.
+    pub fn next_frame(&mut self) -> Option<Frame> {
+        match self.state {
+            ConnectionTransmitterState::RequestToSend => {
+                let data_length = (self.data.len() as u16).to_le_bytes();
+                let pgn_bytes = self.pgn.to_le_bytes();
+
+                self.state = ConnectionTransmitterState::ClearToSend;
+                self.timer.arm(TIMEOUT_T3);
+
+                Some(connection_management_frame(
+                    self.sa,
+                    self.da,
+                    &[
+                        ConnectionManagement::RequestToSend as u8,
+                        data_length[0],
+                        data_length[1],
+                        self.packet_count() as u8,
+                        PDU_NOT_AVAILABLE,
+                        pgn_bytes[0],
+                        pgn_bytes[1],
+                        pgn_bytes[2],
+                    ],
+                ))
+            }
+            ConnectionTransmitterState::DataTransfer { sequence, last } => {
+                let start = (sequence as usize - 1) * DATA_FRAME_SIZE;
+                let end = (start + DATA_FRAME_SIZE).min(self.data.len());
+
+                let frame = data_transfer_frame(self.sa, self.da, sequence, &self.data[start..end]);
+
+                self.state = if sequence < last {
+                    ConnectionTransmitterState::DataTransfer {
+                        sequence: sequence + 1,
+                        last,
+                    }
+                } else if last as usize == self.packet_count() {
+                    self.timer.arm(TIMEOUT_T3);
+                    ConnectionTransmitterState::EndOfMessageAcknowledgment
+                } else {
+                    self.timer.arm(TIMEOUT_T3);
+                    ConnectionTransmitterState::ClearToSend
+                };
+
+                Some(frame)
+            }
+            _ => None,
+        }
+    }
+
+    /// Advance the session to the current time.
+    ///
+    /// Returns the next frame to send, or the abort frame if the receiver did not respond
+    /// within T3, or did not resume within T4 after holding the connection.
+    pub fn tick(&mut self, now: Duration) -> Option<Frame> {
+        if self.timer.is_expired(now) {
+            return Some(self.abort(AbortReason::Timeout));
+        }
+
+        let frame = self.next_frame();
+        self.timer.start(now);
+        frame
+    }
+
+    /// Process a frame from the receiver.
+    ///
+    /// Frames not part of this session are ignored. On error the session is left unchanged,
+    /// the caller should abort the session with the abort reason of the error.
+    pub fn from_frame(&mut self, frame: &Frame) -> Result<(), TransportError> {
+        if frame.id().pgn() != PGN::TransportProtocolConnectionManagement
+            || frame.id().source_address() != self.da
+            || frame.id().destination_address() != Some(self.sa)
+        {
+            return Ok(());
+        }
+
+        let data = frame.as_ref();
+        if data.len() != PDU_MAX_LENGTH {
+            return Err(TransportError::InvalidFrame);
+        }
+        if PGN::from_le_bytes([data[5], data[6], data[7]]) != self.pgn {
+            return Ok(());
+        }
+
+        match ConnectionManagement::from_value(data[0]) {
+            Some(ConnectionManagement::ClearToSend) => {
+                let packets = data[1];
+                let sequence = data[2];
+
+                if let ConnectionTransmitterState::DataTransfer { .. } = self.state {
+                    return Err(TransportError::ClearToSendInProgress);
+                }
+
+                if packets == 0 {
+                    self.state = ConnectionTransmitterState::ClearToSend;
+                    self.timer.arm(TIMEOUT_T4);
+                } else if sequence > 0 && sequence as usize <= self.packet_count() {
+                    let last = (sequence as usize + packets as usize - 1).min(self.packet_count());
+
+                    self.state = ConnectionTransmitterState::DataTransfer {
+                        sequence,
+                        last: last as u8,
+                    };
+                    self.timer.disarm();
+                } else {
+                    return Err(TransportError::BadSequence);
+                }
+            }
+            Some(ConnectionManagement::EndOfMessageAcknowledgment) => {
+                self.state = ConnectionTransmitterState::Complete;
+                self.timer.disarm();
+            }
+            Some(ConnectionManagement::Abort) => {
+                let reason = AbortReason::from_value(data[1]);
+
+                self.state = ConnectionTransmitterState::Aborted(reason);
+                self.timer.disarm();
+
+                return Err(TransportError::Aborted(reason));
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ConnectionReceiverState {
+    /// Waiting for a request to send.
+    RequestToSend,
+    /// Clear to send for the next window is pending.
+    ClearToSend(u8),
+    /// Receiving the packets in the current window.
+    DataTransfer {
+        /// Next expected sequence number.
+        sequence: u8,
+        /// Last sequence number in the window.
+        last: u8,
+    },
+    /// End of message acknowledgment is pending.
+    EndOfMessageAcknowledgment,
+    /// Message was received and acknowledged.
+    Complete,
+    /// Session was aborted.
+    Aborted(AbortReason),
+}
+
+/// Connection mode data transfer receiver.
+///
+/// The receiver accepts a request to send (RTS) addressed to its source address, clears
+/// windows of packets (CTS) and acknowledges (EoMA) the message once all packets are received.
+pub struct ConnectionReceiver {
+    sa: u8,
+    da: u8,
+    pgn: PGN,
+    data: [u8; DATA_MAX_LENGTH],
+    data_length: usize,
+    tail: usize,
+    window: u8,
+    /// Window of the current session, limited by the maximum packets of the transmitter.
+    session_window: u8,
+    hold: bool,
+    state: ConnectionReceiverState,
+    timer: Timer,
+}
+
+impl ConnectionReceiver {
+    pub fn new(sa: u8) -> Self {
+        Self {
+            sa,
+            da: PDU_NOT_AVAILABLE,
+            pgn: PGN::Other(0),
+            data: [PDU_NOT_AVAILABLE; DATA_MAX_LENGTH],
+            data_length: 0,
+            tail: 0,
+            window: PACKET_MAX_COUNT as u8,
+            session_window: PACKET_MAX_COUNT as u8,
+            hold: false,
+            state: ConnectionReceiverState::RequestToSend,
+            timer: Timer::new(),
+        }
+    }
+
+    /// Set the maximum number of packets per clear to send.
+    pub fn with_window(mut self, packets: u8) -> Self {
+        self.window = packets.max(1);
+        self
+    }
+
+    /// Returns the source address of the transmitter.
+    #[inline]
+    pub fn source_address(&self) -> u8 {
+        self.da
+    }
+
+    /// Returns the PGN of the transported message.
+    #[inline]
+    pub fn pgn(&self) -> PGN {
+        self.pgn
+    }
+
+    /// Returns a slice of the transport data.
+    pub fn data(&self) -> &[u8] {
+        &self.data[..self.tail]
+    }
+
+    /// Returns the length of the transport data.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.tail
+    }
+
+    /// Returns `true` if the transport data is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.tail == 0
+    }
+
+    /// Returns the current state of the session.
+    #[inline]
+    pub fn state(&self) -> ConnectionReceiverState {
+        self.state
+    }
+
+    /// Returns `true` if the message was received and acknowledged.
+    #[inline]
+    pub fn is_complete(&self) -> bool {
+        self.state == ConnectionReceiverState::Complete
+    }
+
+    pub fn packet_count(&self) -> usize {
+        packet_count(self.data_length)
+    }
+
+    /// Returns `true` if a session is in progress.
+    pub fn is_active(&self) -> bool {
+        matches!(
+            self.state,
+            ConnectionReceiverState::ClearToSend(_)
+                | ConnectionReceiverState::DataTransfer { .. }
+                | ConnectionReceiverState::EndOfMessageAcknowledgment
+        )
+    }
+
+    /// Hold the connection open.
+    ///
+    /// While held, the next window is not cleared and a clear to send with zero packets is
+    /// sent every Th to keep the transmitter waiting.
+    pub fn hold(&mut self) {
+        self.hold = true;
+    }
+
+    /// Resume a held connection.
+    pub fn resume(&mut self) {
+        if self.hold {
+            self.hold = false;
+            self.timer.disarm();
+        }
+    }
+
+    /// Abort the session.
+    ///
+    /// Returns the abort frame to send to the transmitter.
+    pub fn abort(&mut self, reason: AbortReason) -> Frame {
+        self.state = ConnectionReceiverState::Aborted(reason);
+        self.timer.disarm();
+
+        ConnectionAbort {
+            reason,
+            pgn: self.pgn,
+        }
+        .to_frame(self.sa, self.da)
+    }
+
+    fn clear_to_send_frame(&self, packets: u8, sequence: u8) -> Frame {
+        let pgn_bytes = self.pgn.to_le_bytes();
+
+        connection_management_frame(
+            self.sa,
+            self.da,
+            &[
+                ConnectionManagement::ClearToSend as u8,
+                packets,
+                sequence,
+                PDU_NOT_AVAILABLE,
+                PDU_NOT_AVAILABLE,
+                pgn_bytes[0],
+                pgn_bytes[1],
+                pgn_bytes[2],
+            ],
+        )
+    }
+
+    /// Returns the next frame to send, if any.
+    pub fn next_frame(&mut self) -> Option<Frame> {
+        match self.state {
+            ConnectionReceiverState::ClearToSend(sequence) => {
+                let remaining = self.packet_count() - (sequence as usize - 1);
+                let packets = remaining.min(self.session_window as usize) as u8;
+
+                self.state = ConnectionReceiverState::DataTransfer {
+                    sequence,
+                    last: sequence + (packets - 1),
+                };
+                self.timer.arm(TIMEOUT_T2);
+
+                Some(self.clear_to_send_frame(packets, sequence))
+            }
+            ConnectionReceiverState::EndOfMessageAcknowledgment => {
+                let data_length = (self.data_length as u16).to_le_bytes();
+                let pgn_bytes = self.pgn.to_le_bytes();
+
+                self.state = ConnectionReceiverState::Complete;
+
+                Some(connection_management_frame(
+                    self.sa,
+                    self.da,
+                    &[
+                        ConnectionManagement::EndOfMessageAcknowledgment as u8,
+                        data_length[0],
+                        data_length[1],
+                        self.packet_count() as u8,
+                        PDU_NOT_AVAILABLE,
+                        pgn_bytes[0],
+                        pgn_bytes[1],
+                        pgn_bytes[2],
+                    ],
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    /// Advance the session to the current time.
+    ///
+    /// Returns the next frame to send, or the abort frame if the transmitter did not send
+    /// the next data packet within T1, or the first packet of a window within T2.
+    pub fn tick(&mut self, now: Duration) -> Option<Frame> {
+        if let ConnectionReceiverState::ClearToSend(sequence) = self.state {
+            if self.hold {
+                if self.timer.is_armed() && !self.timer.is_expired(now) {
+                    return None;
+                }
+
+                self.timer.arm_at(now, TIMEOUT_TH);
+
+                return Some(self.clear_to_send_frame(0, sequence));
+            }
+        } else if self.timer.is_expired(now) {
+            return Some(self.abort(AbortReason::Timeout));
+        }
+
+        let frame = self.next_frame();
+        self.timer.start(now);
+        frame
+    }
+
+    /// Process a frame from the transmitter.
+    ///
+    /// Frames not addressed to this receiver are ignored. On error the session is left
+    /// unchanged. A request to send from another transmitter while a session is in progress
+    /// fails with `TransportError::AlreadyInSession`, the caller should reject it with a
+    /// `ConnectionAbort` to that transmitter. Any other error should abort the session with
+    /// the abort reason of the error.
+    pub fn from_frame(&mut self, frame: &Frame) -> Result<(), TransportError> {
+        if frame.id().destination_address() != Some(self.sa) {
+            return Ok(());
+        }
+
+        let pgn = frame.id().pgn();
+        let data = frame.as_ref();
+
+        if pgn == PGN::TransportProtocolConnectionManagement {
+            if data.len() != PDU_MAX_LENGTH {
+                return Err(TransportError::InvalidFrame);
+            }
+
+            match ConnectionManagement::from_value(data[0]) {
+                Some(ConnectionManagement::RequestToSend) => {
+                    if self.is_active() && frame.id().source_address() != self.da {
+                        return Err(TransportError::AlreadyInSession);
+                    }
+
+                    let data_length = u16::from_le_bytes([data[1], data[2]]) as usize;
+                    if data_length == 0
+                        || data_length > DATA_MAX_LENGTH
+                        || data[3] as usize != packet_count(data_length)
+                    {
+                        return Err(TransportError::BadLength);
+                    }
+
+                    self.da = frame.id().source_address();
+                    self.pgn = PGN::from_le_bytes([data[5], data[6], data[7]]);
+                    self.data_length = data_length;
+                    self.tail = 0;
+                    self.session_window = match data[4] {
+                        0 => self.window,
+                        packets => self.window.min(packets),
+                    };
+                    self.state = ConnectionReceiverState::ClearToSend(1);
+                    self.timer.disarm();
+                }
+                Some(ConnectionManagement::Abort) if frame.id().source_address() == self.da => {
+                    let reason = AbortReason::from_value(data[1]);
+
+                    self.state = ConnectionReceiverState::Aborted(reason);
+                    self.timer.disarm();
+
+                    return Err(TransportError::Aborted(reason));
+                }
+                _ => {}
+            }
+        } else if pgn == PGN::TransportProtocolDataTransfer
+            && frame.id().source_address() == self.da
+        {
+            if data.len() != PDU_MAX_LENGTH {
+                return Err(TransportError::InvalidFrame);
+            }
+
+            let (sequence, last) = match self.state {
+                ConnectionReceiverState::DataTransfer { sequence, last } => (sequence, last),
+                _ => return Err(TransportError::UnexpectedDataTransfer),
+            };
+
+            if sequence > 1 && data[0] == sequence - 1 {
+                return Err(TransportError::DuplicateSequence);
+            } else if data[0] != sequence {
+                return Err(TransportError::BadSequence);
+            }
+
+            let start = (sequence as usize - 1) * DATA_FRAME_SIZE;
+            let end = (start + DATA_FRAME_SIZE).min(self.data_length);
+
+            self.data[start..end].copy_from_slice(&data[1..(end - start + 1)]);
+            self.tail = end;
+
+            self.state = if sequence < last {
+                self.timer.arm(TIMEOUT_T1);
+                ConnectionReceiverState::DataTransfer {
+                    sequence: sequence + 1,
+                    last,
+                }
+            } else if last as usize == self.packet_count() {
+                self.timer.disarm();
+                ConnectionReceiverState::EndOfMessageAcknowledgment
+            } else {
+                self.timer.disarm();
+                ConnectionReceiverState::ClearToSend(last + 1)
+            };
+        }
+
+        Ok(())
+    }
+}
+
+impl AsRef<[u8]> for ConnectionReceiver {
+    fn as_ref(&self) -> &[u8] {
+        &self.data[..self.tail]
+    }
+}
+
+/// Reassembled transport message.
+///
+/// Holds the identifier, PGN and payload of the transported message.
+pub type TransportMessage<'a> = (Id, PGN, &'a [u8]);
+
+enum Session {
+    Broadcast(BroadcastTransport),
+    Connection(ConnectionReceiver),
+}
+
+impl Session {
+    /// Returns `true` if the session is finished and can be released.
+    fn is_finished(&self) -> bool {
+        match self {
+            Session::Broadcast(transport) => {
+                transport.is_complete()
+                    || matches!(transport.state(), BroadcastTransportState::Aborted(_))
+            }
+            Session::Connection(receiver) => matches!(
+                receiver.state(),
+                ConnectionReceiverState::Complete | ConnectionReceiverState::Aborted(_)
+            ),
+        }
+    }
+
+    /// Returns `true` if all data of the session is received.
+    fn is_received(&self) -> bool {
+        match self {
+            Session::Broadcast(transport) => transport.is_complete(),
+            Session::Connection(receiver) => {
+                receiver.state() == ConnectionReceiverState::EndOfMessageAcknowledgment
+            }
+        }
+    }
+}
+
+struct Slot {
+    /// Source address of the transmitter.
+    sa: u8,
+    /// Destination address of the session.
+    da: u8,
+    /// Transport session.
+    session: Session,
+    /// Abort frame pending to be sent.
+    abort: Option<Frame>,
+}
+
+/// Transport reassembly manager.
+///
+/// The manager accepts every incoming frame and reassembles up to `N` concurrent transport
+/// sessions. Broadcast sessions are tracked for every transmitter on the bus, connection mode
+/// sessions only for those addressed to the source address of the manager. Sessions are keyed
+/// on the source and destination address. Frames to send (CTS, EoMA and aborts) are returned
+/// from `tick`.
+pub struct ReassemblyManager<const N: usize> {
+    sa: u8,
+    slots: [Option<Slot>; N],
+    reject: Option<Frame>,
+}
+
+impl<const N: usize> ReassemblyManager<N> {
+    pub fn new(sa: u8) -> Self {
+        Self {
+            sa,
+            slots: core::array::from_fn(|_| None),
+            reject: None,
+        }
+    }
+
+    /// Returns the number of sessions in progress.
+    pub fn session_count(&self) -> usize {
+        self.slots.iter().filter(|slot| slot.is_some()).count()
+    }
+
+    fn release_finished(&mut self) {
+        for slot in self.slots.iter_mut() {
+            if slot
+                .as_ref()
+                .is_some_and(|slot| slot.abort.is_none() && slot.session.is_finished())
+            {
+                *slot = None;
+            }
+        }
+    }
+
+    /// Find the session between the source and destination address.
+    ///
+    /// Sessions are not keyed on the PGN, data transfer packets do not carry the PGN. A node
+    /// can only have one session open per destination address at a time, a new BAM or RTS
+    /// for another PGN replaces the session in progress.
+    fn find(&self, sa: u8, da: u8) -> Option<usize> {
+        self.slots.iter().position(|slot| {
+            slot.as_ref()
+                .is_some_and(|slot| slot.sa == sa && slot.da == da)
+        })
+    }
+
+    fn allocate(&mut self, sa: u8, da: u8, session: Session) -> Option<usize> {
+        let index = self.slots.iter().position(|slot| slot.is_none())?;
+
+        self.slots[index] = Some(Slot {
+            sa,
+            da,
+            session,
+            abort: None,
+        });
+
+        Some(index)
+    }
+
+    /// Process an incoming frame.
+    ///
+    /// Returns the identifier, PGN and payload of the transported message once all its data
+    /// is received. Connection mode sessions are aborted on error, broadcast sessions are
+    /// dropped.
+    pub fn from_frame(
+        &mut self,
+        frame: &Frame,
+    ) -> Result<Option<TransportMessage<'_>>, TransportError> {
+        self.release_finished();
+
+        let pgn = frame.id().pgn();
+        let sa = frame.id().source_address();
+        let da = match frame.id().destination_address() {
+            Some(da) => da,
+            None => return Ok(None),
+        };
+
+        if pgn != PGN::TransportProtocolConnectionManagement
+            && pgn != PGN::TransportProtocolDataTransfer
+        {
+            return Ok(None);
+        }
+        if da != 0xff && da != self.sa {
+            return Ok(None);
+        }
+
+        let index = match self.find(sa, da) {
+            Some(index) => index,
+            None if pgn == PGN::TransportProtocolConnectionManagement => {
+                let control = match frame.pdu().first() {
+                    Some(control) => *control,
+                    None => return Ok(None),
+                };
+
+                let session = match ConnectionManagement::from_value(control) {
+                    Some(ConnectionManagement::BroadcastAnnounceMessage) if da == 0xff => {
+                        Session::Broadcast(BroadcastTransport::new(sa, PGN::Other(0)))
+                    }
+                    Some(ConnectionManagement::RequestToSend) if da == self.sa => {
+                        Session::Connection(ConnectionReceiver::new(self.sa))
+                    }
+                    _ => return Ok(None),
+                };
+
+                match self.allocate(sa, da, session) {
+                    Some(index) => index,
+                    None if da == 0xff => return Err(TransportError::InsufficientResources),
+                    None => {
+                        let data = frame.as_ref();
+                        if data.len() == PDU_MAX_LENGTH {
+                            self.reject = Some(
+                                ConnectionAbort {
+                                    reason: AbortReason::AlreadyInSession,
+                                    pgn: PGN::from_le_bytes([data[5], data[6], data[7]]),
+                                }
+                                .to_frame(self.sa, sa),
+                            );
+                        }
+                        return Err(TransportError::AlreadyInSession);
+                    }
+                }
+            }
+            None => return Ok(None),
+        };
+
+        let slot = self.slots[index].as_mut().unwrap();
+
+        let result = match &mut slot.session {
+            Session::Broadcast(transport) => transport.from_frame(frame),
+            Session::Connection(receiver) => receiver.from_frame(frame),
+        };
+
+        if let Err(err) = result {
+            match &mut slot.session {
+                Session::Connection(receiver) if !matches!(err, TransportError::Aborted(_)) => {
+                    slot.abort = Some(receiver.abort(err.abort_reason()));
+                }
+                _ => self.slots[index] = None,
+            }
+            return Err(err);
+        }
+
+        let slot = self.slots[index].as_ref().unwrap();
+        if !slot.session.is_received() {
+            return Ok(None);
+        }
+
+        let (pgn, data) = match &slot.session {
+            Session::Broadcast(transport) => (transport.pgn(), transport.data()),
+            Session::Connection(receiver) => (receiver.pgn(), receiver.data()),
+        };
+
+        let id = IdBuilder::from_pgn(pgn).sa(slot.sa).da(slot.da).build();
+
+        Ok(Some((id, pgn, data)))
+    }
+
+    /// Advance all sessions to the current time.
+    ///
+    /// Returns the next frame to send, if any. Call repeatedly until no frame is returned.
+    pub fn tick(&mut self, now: Duration) -> Option<Frame> {
+        if let Some(frame) = self.reject.take() {
+            return Some(frame);
+        }
+
+        for slot in self.slots.iter_mut() {
+            let Some(inner) = slot.as_mut() else {
+                continue;
+            };
+
+            if let Some(frame) = inner.abort.take() {
+                *slot = None;
+                return Some(frame);
+            }
+
+            let frame = match &mut inner.session {
+                Session::Broadcast(transport) => transport.tick(now),
+                Session::Connection(receiver) => receiver.tick(now),
+            };
+
+            if inner.session.is_finished() {
+                *slot = None;
+            }
+
+            if frame.is_some() {
+                return frame;
+            }
+        }
+
+        None
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ExtendedConnectionManagement {
+    RequestToSend = 0x14,
+    ClearToSend = 0x15,
+    DataPacketOffset = 0x16,
+    EndOfMessageAcknowledgment = 0x17,
+    Abort = 0xff,
+}
+
+impl ExtendedConnectionManagement {
+    pub fn from_value(value: u8) -> Option<Self> {
+        match value {
+            0x14 => Some(Self::RequestToSend),
+            0x15 => Some(Self::ClearToSend),
+            0x16 => Some(Self::DataPacketOffset),
+            0x17 => Some(Self::EndOfMessageAcknowledgment),
+            0xff => Some(Self::Abort),
+            _ => None,
+        }
+    }
+}
+
+/// Create an extended connection management frame.
+fn extended_connection_management_frame(sa: u8, da: u8, pdu: &[u8]) -> Frame {
+    FrameBuilder::new(
+        IdBuilder::from_pgn(PGN::ExtendedTransportProtocolConnectionManagement)
+            .priority(7)
+            .sa(sa)
+            .da(da)
+            .build(),
+    )
+    .copy_from_slice(pdu)
+    .build()
+}
+
+/// Create an extended data transfer frame.
+fn extended_data_transfer_frame(sa: u8, da: u8, sequence: u8, data_chunk: &[u8]) -> Frame {
+    let mut frame_builder = FrameBuilder::new(
+        IdBuilder::from_pgn(PGN::ExtendedTransportProtocolDataTransfer)
+            .priority(7)
+            .sa(sa)
+            .da(da)
+            .build(),
+    );
+
+    let payload = frame_builder.as_mut();
+    payload[0] = sequence;
+    payload[1..(data_chunk.len() + 1)].copy_from_slice(data_chunk);
+
+    frame_builder.set_len(8).build()
+}
+
+/// Create an extended connection management frame with a 24-bit packet number.
+fn extended_packet_frame(
+    sa: u8,
+    da: u8,
+    control: ExtendedConnectionManagement,
+    packets: u8,
+    packet: u32,
+    pgn: PGN,
+) -> Frame {
+    let packet_bytes = packet.to_le_bytes();
+    let pgn_bytes = pgn.to_le_bytes();
+
+    extended_connection_management_frame(
+        sa,
+        da,
+        &[
+            control as u8,
+            packets,
+            packet_bytes[0],
+            packet_bytes[1],
+            packet_bytes[2],
+            pgn_bytes[0],
+            pgn_bytes[1],
+            pgn_bytes[2],
+        ],
+    )
+}
+
+/// Create an extended connection management frame with a 32-bit message size.
+fn extended_size_frame(
+    sa: u8,
+    da: u8,
+    control: ExtendedConnectionManagement,
+    data_length: u32,
+    pgn: PGN,
+) -> Frame {
+    let length_bytes = data_length.to_le_bytes();
+    let pgn_bytes = pgn.to_le_bytes();
+
+    extended_connection_management_frame(
+        sa,
+        da,
+        &[
+            control as u8,
+            length_bytes[0],
+            length_bytes[1],
+            length_bytes[2],
+            length_bytes[3],
+            pgn_bytes[0],
+            pgn_bytes[1],
+            pgn_bytes[2],
+        ],
+    )
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ExtendedTransmitterState {
+    /// Request to send is pending.
+    RequestToSend,
+    /// Waiting for the receiver to clear the next window.
+    ClearToSend,
+    /// Data packet offset for the cleared window is pending.
+    DataPacketOffset {
+        /// First packet number in the window.
+        packet: u32,
+        /// Number of packets in the window.
+        packets: u8,
+    },
+    /// Sending the packets in the current window.
+    DataTransfer {
+        /// Packet offset of the window.
+        offset: u32,
+        /// Next sequence number to send.
+        sequence: u8,
+        /// Last sequence number in the window.
+        last: u8,
+    },
+    /// Waiting for the end of message acknowledgment.
+    EndOfMessageAcknowledgment,
+    /// Receiver acknowledged the message.
+    Complete,
+    /// Session was aborted.
+    Aborted(AbortReason),
+}
+
+/// Extended transport data source.
+///
+/// The source copies the message data at the byte offset into the buffer, which holds the
+/// data of a single packet. Returns `false` if the data is not available, which aborts the
+/// session.
+pub type ExtendedDataSource<'a> = &'a mut dyn FnMut(usize, &mut [u8]) -> bool;
+
+enum ExtendedData<'a> {
+    Slice(&'a [u8]),
+    Source(ExtendedDataSource<'a>),
+}
+
+/// Extended transport protocol transmitter.
+///
+/// The extended transport protocol (ETP) transfers messages larger than `DATA_MAX_LENGTH` to a
+/// single destination. The transmitter either borrows the message data, or reads the data of
+/// every packet from a data source as it is sent, so the message never has to be held in
+/// memory as a whole.
+pub struct ExtendedTransmitter<'a> {
+    sa: u8,
+    da: u8,
+    pgn: PGN,
+    data: ExtendedData<'a>,
+    data_length: usize,
+    state: ExtendedTransmitterState,
+    timer: Timer,
+}
+
+/// Returns `true` if the length requires the extended transport protocol.
+fn is_extended_length(length: usize) -> bool {
+    length > DATA_MAX_LENGTH && length <= EXTENDED_DATA_MAX_LENGTH
+}
+
+impl<'a> ExtendedTransmitter<'a> {
+    pub fn new(sa: u8, da: u8, pgn: PGN) -> Self {
+        Self {
+            sa,
+            da,
+            pgn,
+            data: ExtendedData::Slice(&[]),
+            data_length: 0,
+            state: ExtendedTransmitterState::RequestToSend,
+            timer: Timer::new(),
+        }
+    }
+
+    /// Set the message data.
+    ///
+    /// Fails with `TransportError::BadLength` if the data is not longer than `DATA_MAX_LENGTH`
+    /// bytes, or longer than `EXTENDED_DATA_MAX_LENGTH` bytes.
+    pub fn with_data(mut self, data: &'a [u8]) -> Result<Self, TransportError> {
+        if !is_extended_length(data.len()) {
+            return Err(TransportError::BadLength);
+        }
+
+        self.data = ExtendedData::Slice(data);
+        self.data_length = data.len();
+        Ok(self)
+    }
+
+    /// Set the data source of a message of the given length.
+    ///
+    /// The data of every packet is read from the source as it is sent. Fails with
+    /// `TransportError::BadLength` if the length is not longer than `DATA_MAX_LENGTH` bytes,
+    /// or longer than `EXTENDED_DATA_MAX_LENGTH` bytes.
+    pub fn with_source(
+        mut self,
+        length: usize,
+        source: ExtendedDataSource<'a>,
+    ) -> Result<Self, TransportError> {
+        if !is_extended_length(length) {
+            return Err(TransportError::BadLength);
+        }
+
+        self.data = ExtendedData::Source(source);
+        self.data_length = length;
+        Ok(self)
+    }
+
+    /// Returns the length of the transport data.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data_length
+    }
+
+    /// Returns `true` if the transport data is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data_length == 0
+    }
+
+    /// Returns the current state of the session.
+    #[inline]
+    pub fn state(&self) -> ExtendedTransmitterState {
+        self.state
+    }
+
+    /// Returns `true` if the receiver acknowledged the message.
+    #[inline]
+    pub fn is_complete(&self) -> bool {
+        self.state == ExtendedTransmitterState::Complete
+    }
+
+    pub fn packet_count(&self) -> u32 {
+        packet_count(self.data_length) as u32
+    }
+
+    /// Abort the session.
+    ///
+    /// Returns the abort frame to send to the receiver.
+    pub fn abort(&mut self, reason: AbortReason) -> Frame {
+        self.state = ExtendedTransmitterState::Aborted(reason);
+        self.timer.disarm();
+
+        extended_connection_management_frame(
+            self.sa,
+            self.da,
+            &ConnectionAbort {
+                reason,
+                pgn: self.pgn,
+            }
+            .to_pdu(),
+        )
+    }
+
+    /// Returns the next frame to send, if any.
+    ///
+    /// No frame is returned while the transmitter is waiting for the receiver.
+    pub fn next_frame(&mut self) -> Option<Frame> {
+        match self.state {
+            ExtendedTransmitterState::RequestToSend => {
+                self.state = ExtendedTransmitterState::ClearToSend;
+                self.timer.arm(TIMEOUT_T3);
+
+                Some(extended_size_frame(
+                    self.sa,
+                    self.da,
+                    ExtendedConnectionManagement::RequestToSend,
+                    self.data_length as u32,
+                    self.pgn,
+                ))
+            }
+            ExtendedTransmitterState::DataPacketOffset { packet, packets } => {
+                self.state = ExtendedTransmitterState::DataTransfer {
+                    offset: packet - 1,
+                    sequence: 1,
+                    last: packets,
+                };
+
+                Some(extended_packet_frame(
+                    self.sa,
+                    self.da,
+                    ExtendedConnectionManagement::DataPacketOffset,
+                    packets,
+                    packet - 1,
+                    self.pgn,
+                ))
+            }
+            ExtendedTransmitterState::DataTransfer {
+                offset,
+                sequence,
+                last,
+            } => {
+                let start = (offset as usize + sequence as usize - 1) * DATA_FRAME_SIZE;
+                let end = (start + DATA_FRAME_SIZE).min(self.data_length);
+
+                let mut chunk = [PDU_NOT_AVAILABLE; DATA_FRAME_SIZE];
+                let chunk = &mut chunk[..end - start];
+
+                let available = match &mut self.data {
+                    ExtendedData::Slice(data) => {
+                        chunk.copy_from_slice(&data[start..end]);
+                        true
+                    }
+                    ExtendedData::Source(source) => source(start, chunk),
+                };
+                if !available {
+                    return Some(self.abort(AbortReason::Other));
+                }
+
+                let frame = extended_data_transfer_frame(self.sa, self.da, sequence, chunk);
+
+                self.state = if sequence < last {
+                    ExtendedTransmitterState::DataTransfer {
+                        offset,
+                        sequence: sequence + 1,
+                        last,
+                    }
+                } else if offset + last as u32 == self.packet_count() {
+                    self.timer.arm(TIMEOUT_T3);
+                    ExtendedTransmitterState::EndOfMessageAcknowledgment
+                } else {
+                    self.timer.arm(TIMEOUT_T3);
+                    ExtendedTransmitterState::ClearToSend
+                };
+
+                Some(frame)
+            }
+            _ => None,
+        }
+    }
+
+    /// Advance the session to the current time.
+    ///
+    /// Returns the next frame to send, or the abort frame if the receiver did not respond
+    /// within T3, or did not resume within T4 after holding the connection.
+    pub fn tick(&mut self, now: Duration) -> Option<Frame> {
+        if self.timer.is_expired(now) {
+            return Some(self.abort(AbortReason::Timeout));
+        }
+
+        let frame = self.next_frame();
+        self.timer.start(now);
+        frame
+    }
+
+    /// Process a frame from the receiver.
+    ///
+    /// Frames not part of this session are ignored. On error the session is left unchanged,
+    /// the caller should abort the session with the abort reason of the error.
+    pub fn from_frame(&mut self, frame: &Frame) -> Result<(), TransportError> {
+        if frame.id().pgn() != PGN::ExtendedTransportProtocolConnectionManagement
+            || frame.id().source_address() != self.da
+            || frame.id().destination_address() != Some(self.sa)
+        {
+            return Ok(());
+        }
+
+        let data = frame.as_ref();
+        if data.len() != PDU_MAX_LENGTH {
+            return Err(TransportError::InvalidFrame);
+        }
+        if PGN::from_le_bytes([data[5], data[6], data[7]]) != self.pgn {
+            return Ok(());
+        }
+
+        match ExtendedConnectionManagement::from_value(data[0]) {
+            Some(ExtendedConnectionManagement::ClearToSend) => {
+                let packets = data[1];
+                let packet = u32::from_le_bytes([data[2], data[3], data[4], 0]);
+
+                if matches!(
+                    self.state,
+                    ExtendedTransmitterState::DataPacketOffset { .. }
+                        | ExtendedTransmitterState::DataTransfer { .. }
+                ) {
+                    return Err(TransportError::ClearToSendInProgress);
+                }
+
+                if packets == 0 {
+                    self.state = ExtendedTransmitterState::ClearToSend;
+                    self.timer.arm(TIMEOUT_T4);
+                } else if packet > 0 && packet <= self.packet_count() {
+                    let remaining = self.packet_count() - packet + 1;
+
+                    self.state = ExtendedTransmitterState::DataPacketOffset {
+                        packet,
+                        packets: remaining.min(packets as u32) as u8,
+                    };
+                    self.timer.disarm();
+                } else {
+                    return Err(TransportError::BadSequence);
+                }
+            }
+            Some(ExtendedConnectionManagement::EndOfMessageAcknowledgment) => {
+                self.state = ExtendedTransmitterState::Complete;
+                self.timer.disarm();
+            }
+            Some(ExtendedConnectionManagement::Abort) => {
+                let reason = AbortReason::from_value(data[1]);
+
+                self.state = ExtendedTransmitterState::Aborted(reason);
+                self.timer.disarm();
+
+                return Err(TransportError::Aborted(reason));
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ExtendedReceiverState {
+    /// Waiting for a request to send.
+    RequestToSend,
+    /// Clear to send for the next window is pending.
+    ClearToSend(u32),
+    /// Waiting for the data packet offset of the cleared window.
+    DataPacketOffset {
+        /// First packet number in the window.
+        packet: u32,
+        /// Number of packets in the window.
+        packets: u8,
+    },
+    /// Receiving the packets in the current window.
+    DataTransfer {
+        /// Packet offset of the window.
+        offset: u32,
+        /// Next expected sequence number.
+        sequence: u8,
+        /// Last sequence number in the window.
+        last: u8,
+    },
+    /// End of message acknowledgment is pending.
+    EndOfMessageAcknowledgment,
+    /// Message was received and acknowledged.
+    Complete,
+    /// Session was aborted.
+    Aborted(AbortReason),
+}
+
+/// Extended transport protocol receiver.
+///
+/// The receiver does not buffer the message. Every data packet is handed to the caller
+/// together with its byte offset in the message, so the data can be streamed to its
+/// destination (e.g. flash memory) as it arrives.
+pub struct ExtendedReceiver {
+    sa: u8,
+    da: u8,
+    pgn: PGN,
+    data_length: usize,
+    window: u8,
+    state: ExtendedReceiverState,
+    timer: Timer,
+}
+
+impl ExtendedReceiver {
+    pub fn new(sa: u8) -> Self {
+        Self {
+            sa,
+            da: PDU_NOT_AVAILABLE,
+            pgn: PGN::Other(0),
+            data_length: 0,
+            window: PACKET_MAX_COUNT as u8,
+            state: ExtendedReceiverState::RequestToSend,
+            timer: Timer::new(),
+        }
+    }
+
+    /// Set the maximum number of packets per clear to send.
+    pub fn with_window(mut self, packets: u8) -> Self {
+        self.window = packets.max(1);
+        self
+    }
+
+    /// Returns the source address of the transmitter.
+    #[inline]
+    pub fn source_address(&self) -> u8 {
+        self.da
+    }
+
+    /// Returns the PGN of the transported message.
+    #[inline]
+    pub fn pgn(&self) -> PGN {
+        self.pgn
+    }
+
+    /// Returns the length of the transported message.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data_length
+    }
+
+    /// Returns `true` if the transported message is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data_length == 0
+    }
+
+    /// Returns the current state of the session.
+    #[inline]
+    pub fn state(&self) -> ExtendedReceiverState {
+        self.state
+    }
+
+    /// Returns `true` if the message was received and acknowledged.
+    #[inline]
+    pub fn is_complete(&self) -> bool {
+        self.state == ExtendedReceiverState::Complete
+    }
+
+    /// Returns `true` if a session is in progress.
+    pub fn is_active(&self) -> bool {
+        matches!(
+            self.state,
+            ExtendedReceiverState::ClearToSend(_)
+                | ExtendedReceiverState::DataPacketOffset { .. }
+                | ExtendedReceiverState::DataTransfer { .. }
+                | ExtendedReceiverState::EndOfMessageAcknowledgment
+        )
+    }
+
+    pub fn packet_count(&self) -> u32 {
+        packet_count(self.data_length) as u32
+    }
+
+    /// Abort the session.
+    ///
+    /// Returns the abort frame to send to the transmitter.
+    pub fn abort(&mut self, reason: AbortReason) -> Frame {
+        self.state = ExtendedReceiverState::Aborted(reason);
+        self.timer.disarm();
+
+        extended_connection_management_frame(
+            self.sa,
+            self.da,
+            &ConnectionAbort {
+                reason,
+                pgn: self.pgn,
+            }
+            .to_pdu(),
+        )
+    }
+
+    /// Returns the next frame to send, if any.
+    pub fn next_frame(&mut self) -> Option<Frame> {
+        match self.state {
+            ExtendedReceiverState::ClearToSend(packet) => {
+                let remaining = self.packet_count() - packet + 1;
+                let packets = remaining.min(self.window as u32) as u8;
+
+                self.state = ExtendedReceiverState::DataPacketOffset { packet, packets };
+                self.timer.arm(TIMEOUT_T2);
+
+                Some(extended_packet_frame(
+                    self.sa,
+                    self.da,
+                    ExtendedConnectionManagement::ClearToSend,
+                    packets,
+                    packet,
+                    self.pgn,
+                ))
+            }
+            ExtendedReceiverState::EndOfMessageAcknowledgment => {
+                self.state = ExtendedReceiverState::Complete;
+
+                Some(extended_size_frame(
+                    self.sa,
+                    self.da,
+                    ExtendedConnectionManagement::EndOfMessageAcknowledgment,
+                    self.data_length as u32,
+                    self.pgn,
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    /// Advance the session to the current time.
+    ///
+    /// Returns the next frame to send, or the abort frame if the transmitter did not send
+    /// the next packet within T1, or the data packet offset of a window within T2.
+    pub fn tick(&mut self, now: Duration) -> Option<Frame> {
+        if self.timer.is_expired(now) {
+            return Some(self.abort(AbortReason::Timeout));
+        }
+
+        let frame = self.next_frame();
+        self.timer.start(now);
+        frame
+    }
+
+    /// Process a frame from the transmitter.
+    ///
+    /// Returns the byte offset in the message and the data of a received data packet. Frames
+    /// not addressed to this receiver are ignored. On error the session is left unchanged, the
+    /// caller should abort the session with the abort reason of the error, or reject a request
+    /// from another transmitter with `TransportError::AlreadyInSession`.
+    pub fn from_frame<'f>(
+        &mut self,
+        frame: &'f Frame,
+    ) -> Result<Option<(usize, &'f [u8])>, TransportError> {
+        if frame.id().destination_address() != Some(self.sa) {
+            return Ok(None);
+        }
+
+        let pgn = frame.id().pgn();
+        let data = frame.as_ref();
+
+        if pgn == PGN::ExtendedTransportProtocolConnectionManagement {
+            if data.len() != PDU_MAX_LENGTH {
+                return Err(TransportError::InvalidFrame);
+            }
+
+            match ExtendedConnectionManagement::from_value(data[0]) {
+                Some(ExtendedConnectionManagement::RequestToSend) => {
+                    if self.is_active() && frame.id().source_address() != self.da {
+                        return Err(TransportError::AlreadyInSession);
+                    }
+
+                    let data_length =
+                        u32::from_le_bytes([data[1], data[2], data[3], data[4]]) as usize;
+                    if data_length <= DATA_MAX_LENGTH || data_length > EXTENDED_DATA_MAX_LENGTH {
+                        return Err(TransportError::BadLength);
+                    }
+
+                    self.da = frame.id().source_address();
+                    self.pgn = PGN::from_le_bytes([data[5], data[6], data[7]]);
+                    self.data_length = data_length;
+                    self.state = ExtendedReceiverState::ClearToSend(1);
+                    self.timer.disarm();
+                }
+                Some(ExtendedConnectionManagement::DataPacketOffset)
+                    if frame.id().source_address() == self.da =>
+                {
+                    let (packet, packets) = match self.state {
+                        ExtendedReceiverState::DataPacketOffset { packet, packets } => {
+                            (packet, packets)
+                        }
+                        _ => return Err(TransportError::UnexpectedDataPacketOffset),
+                    };
+
+                    let offset = u32::from_le_bytes([data[2], data[3], data[4], 0]);
+                    if offset != packet - 1 || data[1] == 0 || data[1] > packets {
+                        return Err(TransportError::BadDataPacketOffset);
+                    }
+
+                    self.state = ExtendedReceiverState::DataTransfer {
+                        offset,
+                        sequence: 1,
+                        last: data[1],
+                    };
+                    self.timer.arm(TIMEOUT_T1);
+                }
+                Some(ExtendedConnectionManagement::Abort)
+                    if frame.id().source_address() == self.da =>
+                {
+                    let reason = AbortReason::from_value(data[1]);
+
+                    self.state = ExtendedReceiverState::Aborted(reason);
+                    self.timer.disarm();
+
+                    return Err(TransportError::Aborted(reason));
+                }
+                _ => {}
+            }
+        } else if pgn == PGN::ExtendedTransportProtocolDataTransfer
+            && frame.id().source_address() == self.da
+        {
+            if data.len() != PDU_MAX_LENGTH {
+                return Err(TransportError::InvalidFrame);
+            }
+
+            let (offset, sequence, last) = match self.state {
+                ExtendedReceiverState::DataTransfer {
+                    offset,
+                    sequence,
+                    last,
+                } => (offset, sequence, last),
+                _ => return Err(TransportError::UnexpectedDataTransfer),
+            };
+
+            if sequence > 1 && data[0] == sequence - 1 {
+                return Err(TransportError::DuplicateSequence);
+            } else if data[0] != sequence {
+                return Err(TransportError::BadSequence);
+            }
+
+            let start = (offset as usize + sequence as usize - 1) * DATA_FRAME_SIZE;
+            let end = (start + DATA_FRAME_SIZE).min(self.data_length);
+
+            self.state = if sequence < last {
+                self.timer.arm(TIMEOUT_T1);
+                ExtendedReceiverState::DataTransfer {
+                    offset,
+                    sequence: sequence + 1,
+                    last,
+                }
+            } else if offset + last as u32 == self.packet_count() {
+                self.timer.disarm();
+                ExtendedReceiverState::EndOfMessageAcknowledgment
+            } else {
+                self.timer.disarm();
+                ExtendedReceiverState::ClearToSend(offset + last as u32 + 1)
+            };
+
+            return Ok(Some((start, &data[1..(end - start + 1)])));
+        }
+
+        Ok(None)
+    }
+}
+
+/// Number of data bytes in the first frame of a fast packet message.
+const FAST_PACKET_FIRST_FRAME_SIZE: usize = 6;
+
+/// Returns the number of frames of a fast packet message.
+fn fast_packet_frame_count(data_length: usize) -> usize {
+    if data_length <= FAST_PACKET_FIRST_FRAME_SIZE {
+        1
+    } else {
+        1 + packet_count(data_length - FAST_PACKET_FIRST_FRAME_SIZE)
+    }
+}
+
+/// Returns the PGN of a fast packet identifier, including the data page.
+///
+/// NMEA 2000 messages are all on data page 1, which is not part of `Id::pgn`.
+fn fast_packet_pgn(id: &Id) -> PGN {
+    PGN::from(((id.data_page() as u32) << 16) | id.pgn_raw())
+}
+
+/// NMEA 2000 fast packet transmitter.
+///
+/// Fast packet messages are sent under the identifier of the message itself. The first frame
+/// carries the sequence counter, the frame counter, the message length and the first 6 data
+/// bytes, every subsequent frame carries the counters and 7 data bytes. The transmitter borrows
+/// the message data.
+pub struct FastPacketTransmitter<'a> {
+    id: Id,
+    sequence: u8,
+    data: &'a [u8],
+    frame: u8,
+}
+
+impl<'a> FastPacketTransmitter<'a> {
+    /// Construct a new transmitter.
+    ///
+    /// The sequence counter distinguishes consecutive messages with the same identifier and
+    /// should be incremented by the caller for every message. Only the lower 3 bits are used.
+    pub fn new(id: Id, sequence: u8) -> Self {
+        Self {
+            id,
+            sequence: sequence & 0b111,
+            data: &[],
+            frame: 0,
+        }
+    }
+
+    /// Set the message data.
+    ///
+    /// Fails with `TransportError::BadLength` if the data is longer than
+    /// `FAST_PACKET_DATA_MAX_LENGTH` bytes.
+    pub fn with_data(mut self, data: &'a [u8]) -> Result<Self, TransportError> {
+        if data.len() > FAST_PACKET_DATA_MAX_LENGTH {
+            return Err(TransportError::BadLength);
+        }
+
+        self.data = data;
+        Ok(self)
+    }
+
+    /// Returns a slice of the message data.
+    pub fn data(&self) -> &[u8] {
+        self.data
+    }
+
+    /// Returns the length of the message data.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns `true` if the message data is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns the sequence counter of the message.
+    #[inline]
+    pub fn sequence(&self) -> u8 {
+        self.sequence
+    }
+
+    /// Returns `true` if all frames are sent.
+    pub fn is_complete(&self) -> bool {
+        self.frame as usize >= self.frame_count()
+    }
+
+    pub fn frame_count(&self) -> usize {
+        fast_packet_frame_count(self.data.len())
+    }
+
+    /// Returns the next frame to send, if any.
+    ///
+    /// No frame is returned once all frames are sent.
+    pub fn next_frame(&mut self) -> Option<Frame> {
+        if self.is_complete() {
+            return None;
+        }
+
+        let mut frame_builder = FrameBuilder::new(self.id);
+
+        let payload = frame_builder.as_mut();
+        payload[0] = (self.sequence << 5) | self.frame;
+
+        if self.frame == 0 {
+            let end = FAST_PACKET_FIRST_FRAME_SIZE.min(self.data.len());
+
+            payload[1] = self.data.len() as u8;
+            payload[2..(end + 2)].copy_from_slice(&self.data[..end]);
+        } else {
+            let start = FAST_PACKET_FIRST_FRAME_SIZE + (self.frame as usize - 1) * DATA_FRAME_SIZE;
+            let end = (start + DATA_FRAME_SIZE).min(self.data.len());
+
+            payload[1..(end - start + 1)].copy_from_slice(&self.data[start..end]);
+        }
+
+        self.frame += 1;
+
+        Some(frame_builder.set_len(8).build())
+    }
+}
+
+/// NMEA 2000 fast packet receiver.
+///
+/// The receiver reassembles a single fast packet message. A first frame always starts a new
+/// message, an incomplete message is discarded.
+pub struct FastPacketReceiver {
+    id: Id,
+    sequence: u8,
+    data: [u8; FAST_PACKET_DATA_MAX_LENGTH],
+    data_length: usize,
+    tail: usize,
+    frame: Option<u8>,
+}
+
+impl FastPacketReceiver {
+    pub fn new() -> Self {
+        Self {
+            id: Id::new(0),
+            sequence: 0,
+            data: [PDU_NOT_AVAILABLE; FAST_PACKET_DATA_MAX_LENGTH],
+            data_length: 0,
+            tail: 0,
+            frame: None,
+        }
+    }
+
+    /// Returns the identifier of the message.
+    #[inline]
+    pub fn id(&self) -> Id {
+        self.id
+    }
+
+    /// Returns the sequence counter of the message.
+    #[inline]
+    pub fn sequence(&self) -> u8 {
+        self.sequence
+    }
+
+    /// Returns a slice of the received data.
+    pub fn data(&self) -> &[u8] {
+        &self.data[..self.tail]
+    }
+
+    /// Returns the length of the received data.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.tail
+    }
+
+    /// Returns `true` if the received data is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.tail == 0
+    }
+
+    /// Returns `true` if a message is being received.
+    pub fn is_active(&self) -> bool {
+        self.frame.is_some() && !self.is_complete()
+    }
+
+    /// Returns `true` if the message is received.
+    pub fn is_complete(&self) -> bool {
+        self.frame.is_some() && self.tail == self.data_length
+    }
+
+    pub fn frame_count(&self) -> usize {
+        fast_packet_frame_count(self.data_length)
+    }
+
+    /// Process a fast packet frame.
+    ///
+    /// The caller is responsible for only passing frames of fast packet PGNs from a single
+    /// transmitter. On error the receiver is left unchanged.
+    pub fn from_frame(&mut self, frame: &Frame) -> Result<(), TransportError> {
+        let data = frame.as_ref();
+        if data.len() != PDU_MAX_LENGTH {
+            return Err(TransportError::InvalidFrame);
+        }
+
+        let sequence = data[0] >> 5;
+        let counter = data[0] & 0b11111;
+
+        if counter == 0 {
+            let data_length = data[1] as usize;
+            if data_length > FAST_PACKET_DATA_MAX_LENGTH {
+                return Err(TransportError::BadLength);
+            }
+
+            let end = FAST_PACKET_FIRST_FRAME_SIZE.min(data_length);
+
+            self.data[..end].copy_from_slice(&data[2..(end + 2)]);
+            self.id = *frame.id();
+            self.sequence = sequence;
+            self.data_length = data_length;
+            self.tail = end;
+            self.frame = Some(0);
+
+            return Ok(());
+        }
+
+        let last = match self.frame {
+            Some(last) if self.is_active() => last,
+            _ => return Err(TransportError::UnexpectedDataTransfer),
+        };
+
+        if sequence != self.sequence {
+            return Err(TransportError::UnexpectedDataTransfer);
+        } else if counter == last {
+            return Err(TransportError::DuplicateSequence);
+        } else if counter != last + 1 {
+            return Err(TransportError::BadSequence);
+        }
+
+        let end = (self.tail + DATA_FRAME_SIZE).min(self.data_length);
+
+        self.data[self.tail..end].copy_from_slice(&data[1..(end - self.tail + 1)]);
+        self.tail = end;
+        self.frame = Some(counter);
+
+        Ok(())
+    }
+}
+
+impl Default for FastPacketReceiver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AsRef<[u8]> for FastPacketReceiver {
+    fn as_ref(&self) -> &[u8] {
+        &self.data[..self.tail]
+    }
+}
+
+/// NMEA 2000 fast packet reassembly manager.
+///
+/// The manager reassembles up to `N` concurrent fast packet messages, keyed by source address
+/// and PGN. Fast packet frames can not be distinguished from single frame messages, the caller
+/// is responsible for only passing frames of fast packet PGNs.
+pub struct FastPacketManager<const N: usize> {
+    slots: [Option<FastPacketReceiver>; N],
+}
+
+impl<const N: usize> FastPacketManager<N> {
+    pub fn new() -> Self {
+        Self {
+            slots: core::array::from_fn(|_| None),
+        }
+    }
+
+    /// Returns the number of messages in progress.
+    pub fn session_count(&self) -> usize {
+        self.slots
+            .iter()
+            .filter(|slot| slot.as_ref().is_some_and(|slot| slot.is_active()))
+            .count()
+    }
+
+    /// Process an incoming fast packet frame.
+    ///
+    /// Returns the identifier, PGN and payload of the message once all its frames are
+    /// received. On error the message in progress is dropped.
+    pub fn from_frame(
+        &mut self,
+        frame: &Frame,
+    ) -> Result<Option<TransportMessage<'_>>, TransportError> {
+        let sa = frame.id().source_address();
+        let pgn = fast_packet_pgn(frame.id());
+
+        let index = match self.slots.iter().position(|slot| {
+            slot.as_ref().is_some_and(|slot| {
+                slot.is_active()
+                    && slot.id().source_address() == sa
+                    && fast_packet_pgn(&slot.id()) == pgn
+            })
+        }) {
+            Some(index) => index,
+            None => match self
+                .slots
+                .iter()
+                .position(|slot| !slot.as_ref().is_some_and(|slot| slot.is_active()))
+            {
+                Some(index) => index,
+                None => return Err(TransportError::InsufficientResources),
+            },
+        };
+
+        let receiver = self.slots[index].get_or_insert_with(FastPacketReceiver::new);
+        if let Err(error) = receiver.from_frame(frame) {
+            self.slots[index] = None;
+            return Err(error);
+        }
+
+        match &self.slots[index] {
+            Some(receiver) if receiver.is_complete() => Ok(Some((
+                receiver.id(),
+                fast_packet_pgn(&receiver.id()),
+                receiver.data(),
+            ))),
+            _ => Ok(None),
+        }
+    }
+}
+
+impl<const N: usize> Default for FastPacketManager<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Id;
+
+    use super::*;
+
+    #[test]
+    fn test_broadcast_transport() {
+        let data = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09];
+
+        let mut transport = BroadcastTransport::new(0x01, PGN::AddressClaimed)
+            .with_data(&data)
+            .unwrap();
+
+        let frame = transport.next_frame().unwrap();
+        assert_eq!(frame.id().as_raw(), 0x1CECFF01);
+        assert_eq!(frame.len(), 8);
+        assert_eq!(
+            frame.as_ref(),
+            &[0x20, 0x09, 0x00, 0x02, 0xFF, 0x00, 0xEE, 0x00]
+        );
+
+        let frame = transport.next_frame().unwrap();
+        assert_eq!(frame.id().as_raw(), 0x1CEBFF01);
+        assert_eq!(frame.len(), 8);
+        assert_eq!(
+            frame.as_ref(),
+            &[0x01, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07]
+        );
+
+        let frame = transport.next_frame().unwrap();
+        assert_eq!(frame.id().as_raw(), 0x1CEBFF01);
+        assert_eq!(frame.len(), 8);
+        assert_eq!(
+            frame.as_ref(),
+            &[0x02, 0x08, 0x09, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]
+        );
+    }
+
+    #[test]
+    fn test_broadcast_transport2() {
+        let frame1 = [0x20, 0x09, 0x00, 0x02, 0xFF, 0x00, 0xEE, 0x00];
+        let frame2 = [0x01, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07];
+        let frame3 = [0x02, 0x08, 0x09, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
+
+        let mut transport = BroadcastTransport::new(0x01, PGN::AddressClaimed);
+
+        transport
+            .from_frame(
+                &FrameBuilder::new(Id::new(0x1CECFF01))
+                    .copy_from_slice(&frame1)
+                    .build(),
+            )
+            .unwrap();
+        assert_eq!(transport.len(), 0);
+        assert_eq!(transport.packet_count(), 2);
+
+        transport
+            .from_frame(
+                &FrameBuilder::new(Id::new(0x1CEBFF01))
+                    .copy_from_slice(&frame2)
+                    .build(),
+            )
+            .unwrap();
+        transport
+            .from_frame(
+                &FrameBuilder::new(Id::new(0x1CEBFF01))
+                    .copy_from_slice(&frame3)
+                    .build(),
+            )
+            .unwrap();
+        assert_eq!(transport.len(), 9);
+        assert_eq!(
+            transport.data(),
+            &[0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09]
+        );
+    }
+
+    #[test]
+    fn test_connection_transport() {
+        let data = [
+            0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E,
+            0x0F, 0x10,
+        ];
+
+        let mut transmitter = ConnectionTransmitter::new(0x01, 0x02, PGN::SoftwareIdentification)
+            .with_data(&data)
+            .unwrap();
+
+        let frame = transmitter.next_frame().unwrap();
+        assert_eq!(frame.id().as_raw(), 0x1CEC0201);
+        assert_eq!(
+            frame.as_ref(),
+            &[0x10, 0x10, 0x00, 0x03, 0xFF, 0xDA, 0xFE, 0x00]
+        );
+        assert!(transmitter.next_frame().is_none());
+
+        let mut receiver = ConnectionReceiver::new(0x02);
+        receiver.from_frame(&frame).unwrap();
+        assert_eq!(receiver.source_address(), 0x01);
+        assert_eq!(receiver.pgn(), PGN::SoftwareIdentification);
+
+        let frame = receiver.next_frame().unwrap();
+        assert_eq!(frame.id().as_raw(), 0x1CEC0102);
+        assert_eq!(
+            frame.as_ref(),
+            &[0x11, 0x03, 0x01, 0xFF, 0xFF, 0xDA, 0xFE, 0x00]
+        );
+
+        transmitter.from_frame(&frame).unwrap();
+        for _ in 0..3 {
+            let frame = transmitter.next_frame().unwrap();
+            assert_eq!(frame.id().as_raw(), 0x1CEB0201);
+            receiver.from_frame(&frame).unwrap();
+        }
+        assert!(transmitter.next_frame().is_none());
+        assert_eq!(
+            transmitter.state(),
+            ConnectionTransmitterState::EndOfMessageAcknowledgment
+        );
+
+        let frame = receiver.next_frame().unwrap();
+        assert_eq!(
+            frame.as_ref(),
+            &[0x13, 0x10, 0x00, 0x03, 0xFF, 0xDA, 0xFE, 0x00]
+        );
+        assert!(receiver.is_complete());
+        assert_eq!(receiver.data(), &data);
+
+        transmitter.from_frame(&frame).unwrap();
+        assert!(transmitter.is_complete());
+    }
+
+    #[test]
+    fn test_connection_transport_window() {
+        let data = [0xAA; 30];
+
+        let mut transmitter = ConnectionTransmitter::new(0x10, 0x20, PGN::ComponentIdentification)
+            .with_data(&data)
+            .unwrap();
+        let mut receiver = ConnectionReceiver::new(0x20).with_window(2);
+
+        let mut clear_to_send = 0;
+        while !transmitter.is_complete() {
+            while let Some(frame) = transmitter.next_frame() {
+                receiver.from_frame(&frame).unwrap();
+            }
+            while let Some(frame) = receiver.next_frame() {
+                if frame.as_ref()[0] == ConnectionManagement::ClearToSend as u8 {
+                    clear_to_send += 1;
+                }
+                transmitter.from_frame(&frame).unwrap();
+            }
+        }
+
+        assert_eq!(clear_to_send, 3);
+        assert_eq!(receiver.len(), 30);
+        assert_eq!(receiver.data(), &data);
+    }
+
+    #[test]
+    fn test_connection_receiver_session_window() {
+        let mut receiver = ConnectionReceiver::new(0x20).with_window(4);
+
+        let request_to_send = |max_packets| {
+            connection_management_frame(
+                0x10,
+                0x20,
+                &[0x10, 0x14, 0x00, 0x03, max_packets, 0x43, 0xFC, 0x00],
+            )
+        };
+
+        receiver.from_frame(&request_to_send(2)).unwrap();
+        let frame = receiver.next_frame().unwrap();
+        assert_eq!(frame.as_ref()[..3], [0x11, 0x02, 0x01]);
+
+        receiver.from_frame(&request_to_send(0xFF)).unwrap();
+        let frame = receiver.next_frame().unwrap();
+        assert_eq!(frame.as_ref()[..3], [0x11, 0x03, 0x01]);
+    }
+
+    #[test]
+    fn test_connection_transport_abort() {
+        let mut transmitter = ConnectionTransmitter::new(0x01, 0x02, PGN::SoftwareIdentification)
+            .with_data(&[0; 9])
+            .unwrap();
+        transmitter.next_frame();
+
+        let result = transmitter.from_frame(
+            &FrameBuilder::new(Id::new(0x1CEC0102))
+                .copy_from_slice(&[0xFF, 0x01, 0xFF, 0xFF, 0xFF, 0xDA, 0xFE, 0x00])
+                .build(),
+        );
+        assert_eq!(
+            result,
+            Err(TransportError::Aborted(AbortReason::AlreadyInSession))
+        );
+        assert_eq!(
+            transmitter.state(),
+            ConnectionTransmitterState::Aborted(AbortReason::AlreadyInSession)
+        );
+        assert!(transmitter.next_frame().is_none());
+    }
+
+    #[test]
+    fn test_broadcast_transport_tick() {
+        let data = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09];
+
+        let mut transport = BroadcastTransport::new(0x01, PGN::AddressClaimed)
+            .with_data(&data)
+            .unwrap();
+
+        let frame = transport.tick(Duration::from_millis(0)).unwrap();
+        assert_eq!(frame.as_ref()[0], 0x20);
+        assert!(transport.tick(Duration::from_millis(10)).is_none());
+
+        let frame = transport.tick(Duration::from_millis(50)).unwrap();
+        assert_eq!(frame.as_ref()[0], 0x01);
+        assert!(transport.tick(Duration::from_millis(60)).is_none());
+
+        let frame = transport.tick(Duration::from_millis(100)).unwrap();
+        assert_eq!(frame.as_ref()[0], 0x02);
+        assert!(transport.is_complete());
+        assert!(transport.tick(Duration::from_millis(150)).is_none());
+    }
+
+    #[test]
+    fn test_broadcast_transmitter() {
+        let data = [
+            0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E,
+            0x0F, 0x10,
+        ];
+
+        let mut transmitter = BroadcastTransmitter::new(0x01, PGN::SoftwareIdentification)
+            .with_data(&data)
+            .unwrap();
+        let mut transport = BroadcastTransport::new(0x01, PGN::SoftwareIdentification)
+            .with_data(&data)
+            .unwrap();
+        assert_eq!(transmitter.packet_count(), 3);
+
+        let mut receiver = BroadcastTransport::new(0x02, PGN::Other(0));
+        while let Some(frame) = transmitter.next_frame() {
+            let expected = transport.next_frame().unwrap();
+            assert_eq!(frame.id().as_raw(), expected.id().as_raw());
+            assert_eq!(frame.as_ref(), expected.as_ref());
+
+            receiver.from_frame(&frame).unwrap();
+        }
+
+        assert!(transmitter.is_complete());
+        assert!(transport.next_frame().is_none());
+        assert_eq!(receiver.pgn(), PGN::SoftwareIdentification);
+        assert_eq!(receiver.data(), &data);
+    }
+
+    #[test]
+    fn test_broadcast_transmitter_tick() {
+        let data = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09];
+
+        let mut transmitter = BroadcastTransmitter::new(0x01, PGN::AddressClaimed)
+            .with_data(&data)
+            .unwrap();
+
+        let frame = transmitter.tick(Duration::from_millis(0)).unwrap();
+        assert_eq!(frame.as_ref()[0], 0x20);
+        assert!(transmitter.tick(Duration::from_millis(10)).is_none());
+
+        let frame = transmitter.tick(Duration::from_millis(50)).unwrap();
+        assert_eq!(
+            frame.as_ref(),
+            &[0x01, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07]
+        );
+        assert!(transmitter.tick(Duration::from_millis(60)).is_none());
+
+        let frame = transmitter.tick(Duration::from_millis(100)).unwrap();
+        assert_eq!(
+            frame.as_ref(),
+            &[0x02, 0x08, 0x09, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]
+        );
+        assert!(transmitter.is_complete());
+        assert!(transmitter.tick(Duration::from_millis(150)).is_none());
+    }
+
+    #[test]
+    fn test_broadcast_transport_timeout() {
+        let mut transport = BroadcastTransport::new(0x01, PGN::AddressClaimed);
+
+        transport
+            .from_frame(
+                &FrameBuilder::new(Id::new(0x1CECFF01))
+                    .copy_from_slice(&[0x20, 0x09, 0x00, 0x02, 0xFF, 0x00, 0xEE, 0x00])
+                    .build(),
+            )
+            .unwrap();
+        assert!(transport.tick(Duration::from_millis(1000)).is_none());
+
+        transport
+            .from_frame(
+                &FrameBuilder::new(Id::new(0x1CEBFF01))
+                    .copy_from_slice(&[0x01, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07])
+                    .build(),
+            )
+            .unwrap();
+        assert!(transport.tick(Duration::from_millis(1100)).is_none());
+        assert_eq!(transport.state(), BroadcastTransportState::DataTransfer(1));
+
+        assert!(transport.tick(Duration::from_millis(1850)).is_none());
+        assert_eq!(
+            transport.state(),
+            BroadcastTransportState::Aborted(AbortReason::Timeout)
+        );
+        assert!(transport.next_frame().is_none());
+        assert_eq!(
+            transport.state(),
+            BroadcastTransportState::Aborted(AbortReason::Timeout)
+        );
+    }
+
+    #[test]
+    fn test_connection_transmitter_timeout() {
+        let mut transmitter = ConnectionTransmitter::new(0x01, 0x02, PGN::SoftwareIdentification)
+            .with_data(&[0; 9])
+            .unwrap();
+
+        assert!(transmitter.tick(Duration::from_millis(100)).is_some());
+        assert!(transmitter.tick(Duration::from_millis(1349)).is_none());
+
+        let frame = transmitter.tick(Duration::from_millis(1350)).unwrap();
+        assert_eq!(frame.id().as_raw(), 0x1CEC0201);
+        assert_eq!(
+            frame.as_ref(),
+            &[0xFF, 0x03, 0xFF, 0xFF, 0xFF, 0xDA, 0xFE, 0x00]
+        );
+        assert_eq!(
+            transmitter.state(),
+            ConnectionTransmitterState::Aborted(AbortReason::Timeout)
+        );
+    }
+
+    #[test]
+    fn test_connection_receiver_timeout() {
+        let mut transmitter = ConnectionTransmitter::new(0x01, 0x02, PGN::SoftwareIdentification)
+            .with_data(&[0; 20])
+            .unwrap();
+        let mut receiver = ConnectionReceiver::new(0x02);
+
+        receiver
+            .from_frame(&transmitter.next_frame().unwrap())
+            .unwrap();
+        transmitter
+            .from_frame(&receiver.tick(Duration::from_millis(0)).unwrap())
+            .unwrap();
+        receiver
+            .from_frame(&transmitter.next_frame().unwrap())
+            .unwrap();
+
+        assert!(receiver.tick(Duration::from_millis(10)).is_none());
+        assert!(receiver.tick(Duration::from_millis(700)).is_none());
+
+        let frame = receiver.tick(Duration::from_millis(760)).unwrap();
+        assert_eq!(frame.id().as_raw(), 0x1CEC0102);
+        assert_eq!(
+            frame.as_ref(),
+            &[0xFF, 0x03, 0xFF, 0xFF, 0xFF, 0xDA, 0xFE, 0x00]
+        );
+
+        assert_eq!(
+            transmitter.from_frame(&frame),
+            Err(TransportError::Aborted(AbortReason::Timeout))
+        );
+        assert_eq!(
+            transmitter.state(),
+            ConnectionTransmitterState::Aborted(AbortReason::Timeout)
+        );
+    }
+
+    #[test]
+    fn test_connection_receiver_hold() {
+        let mut transmitter = ConnectionTransmitter::new(0x01, 0x02, PGN::SoftwareIdentification)
+            .with_data(&[0; 9])
+            .unwrap();
+        let mut receiver = ConnectionReceiver::new(0x02);
+
+        receiver
+            .from_frame(&transmitter.next_frame().unwrap())
+            .unwrap();
+        receiver.hold();
+
+        let frame = receiver.tick(Duration::from_millis(0)).unwrap();
+        assert_eq!(
+            frame.as_ref(),
+            &[0x11, 0x00, 0x01, 0xFF, 0xFF, 0xDA, 0xFE, 0x00]
+        );
+        transmitter.from_frame(&frame).unwrap();
+        assert!(transmitter.tick(Duration::from_millis(0)).is_none());
+
+        assert!(receiver.tick(Duration::from_millis(400)).is_none());
+        transmitter
+            .from_frame(&receiver.tick(Duration::from_millis(500)).unwrap())
+            .unwrap();
+        assert!(transmitter.tick(Duration::from_millis(1000)).is_none());
+
+        receiver.resume();
+        let frame = receiver.tick(Duration::from_millis(1100)).unwrap();
+        assert_eq!(frame.as_ref()[1], 0x02);
+
+        transmitter.from_frame(&frame).unwrap();
+        assert!(transmitter.tick(Duration::from_millis(1100)).is_some());
+    }
+
+    #[test]
+    fn test_broadcast_transport_bad_sequence() {
+        let mut transport = BroadcastTransport::new(0x01, PGN::AddressClaimed);
+
+        assert_eq!(
+            transport.from_frame(
+                &FrameBuilder::new(Id::new(0x1CEBFF01))
+                    .copy_from_slice(&[0x01, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07])
+                    .build(),
+            ),
+            Err(TransportError::UnexpectedDataTransfer)
+        );
+
+        transport
+            .from_frame(
+                &FrameBuilder::new(Id::new(0x1CECFF01))
+                    .copy_from_slice(&[0x20, 0x09, 0x00, 0x02, 0xFF, 0x00, 0xEE, 0x00])
+                    .build(),
+            )
+            .unwrap();
+
+        let data_transfer = |sequence: u8| {
+            FrameBuilder::new(Id::new(0x1CEBFF01))
+                .copy_from_slice(&[sequence, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07])
+                .build()
+        };
+
+        assert_eq!(
+            transport.from_frame(&data_transfer(0)),
+            Err(TransportError::BadSequence)
+        );
+        assert_eq!(
+            transport.from_frame(&data_transfer(2)),
+            Err(TransportError::BadSequence)
+        );
+        transport.from_frame(&data_transfer(1)).unwrap();
+        assert_eq!(
+            transport.from_frame(&data_transfer(1)),
+            Err(TransportError::DuplicateSequence)
+        );
+        transport.from_frame(&data_transfer(2)).unwrap();
+        assert!(transport.is_complete());
+        assert_eq!(
+            transport.from_frame(&data_transfer(3)),
+            Err(TransportError::BadSequence)
+        );
+        assert_eq!(transport.len(), 9);
+    }
+
+    #[test]
+    fn test_broadcast_transport_bad_length() {
+        let mut transport = BroadcastTransport::new(0x01, PGN::AddressClaimed);
+
+        assert_eq!(
+            transport.from_frame(
+                &FrameBuilder::new(Id::new(0x1CECFF01))
+                    .copy_from_slice(&[0x20, 0xFA, 0x06, 0xFF, 0xFF, 0x00, 0xEE, 0x00])
+                    .build(),
+            ),
+            Err(TransportError::BadLength)
+        );
+        assert_eq!(
+            transport.from_frame(
+                &FrameBuilder::new(Id::new(0x1CECFF01))
+                    .copy_from_slice(&[0x20, 0x09, 0x00])
+                    .build(),
+            ),
+            Err(TransportError::InvalidFrame)
+        );
+    }
+
+    #[test]
+    fn test_connection_receiver_already_in_session() {
+        let mut receiver = ConnectionReceiver::new(0x02);
+
+        let request_to_send = |sa: u8| {
+            ConnectionTransmitter::new(sa, 0x02, PGN::SoftwareIdentification)
+                .with_data(&[0; 9])
+                .unwrap()
+                .next_frame()
+                .unwrap()
+        };
+
+        receiver.from_frame(&request_to_send(0x01)).unwrap();
+        assert_eq!(
+            receiver.from_frame(&request_to_send(0x03)),
+            Err(TransportError::AlreadyInSession)
+        );
+        assert_eq!(receiver.source_address(), 0x01);
+
+        let frame = ConnectionAbort {
+            reason: TransportError::AlreadyInSession.abort_reason(),
+            pgn: PGN::SoftwareIdentification,
+        }
+        .to_frame(0x02, 0x03);
+        assert_eq!(frame.id().as_raw(), 0x1CEC0302);
+        assert_eq!(
+            frame.as_ref(),
+            &[0xFF, 0x01, 0xFF, 0xFF, 0xFF, 0xDA, 0xFE, 0x00]
+        );
+        assert_eq!(
+            ConnectionAbort::from_pdu(frame.as_ref()),
+            Ok(ConnectionAbort {
+                reason: AbortReason::AlreadyInSession,
+                pgn: PGN::SoftwareIdentification,
+            })
+        );
+    }
+
+    #[test]
+    fn test_reassembly_manager_broadcast() {
+        let mut manager = ReassemblyManager::<4>::new(0x80);
+
+        let data1 = [0x11; 10];
+        let data2 = [0x22; 12];
+
+        let mut transport1 = BroadcastTransport::new(0x10, PGN::SoftwareIdentification)
+            .with_data(&data1)
+            .unwrap();
+        let mut transport2 = BroadcastTransport::new(0x20, PGN::ComponentIdentification)
+            .with_data(&data2)
+            .unwrap();
+
+        for _ in 0..2 {
+            assert!(manager
+                .from_frame(&transport1.next_frame().unwrap())
+                .unwrap()
+                .is_none());
+            assert!(manager
+                .from_frame(&transport2.next_frame().unwrap())
+                .unwrap()
+                .is_none());
+        }
+        assert_eq!(manager.session_count(), 2);
+
+        let (id, pgn, data) = manager
+            .from_frame(&transport1.next_frame().unwrap())
+            .unwrap()
+            .unwrap();
+        assert_eq!(id.source_address(), 0x10);
+        assert_eq!(pgn, PGN::SoftwareIdentification);
+        assert_eq!(data, &data1);
+
+        let (id, pgn, data) = manager
+            .from_frame(&transport2.next_frame().unwrap())
+            .unwrap()
+            .unwrap();
+        assert_eq!(id.source_address(), 0x20);
+        assert_eq!(pgn, PGN::ComponentIdentification);
+        assert_eq!(data, &data2);
+
+        assert!(manager.tick(Duration::from_millis(0)).is_none());
+        assert_eq!(manager.session_count(), 0);
+    }
+
+    #[test]
+    fn test_reassembly_manager_connection() {
+        let mut manager = ReassemblyManager::<2>::new(0x80);
+
+        let data = [0x33; 20];
+        let mut transmitter = ConnectionTransmitter::new(0x10, 0x80, PGN::SoftwareIdentification)
+            .with_data(&data)
+            .unwrap();
+
+        let frame = transmitter.next_frame().unwrap();
+        assert!(manager.from_frame(&frame).unwrap().is_none());
+
+        let frame = manager.tick(Duration::from_millis(0)).unwrap();
+        assert_eq!(frame.id().as_raw(), 0x1CEC1080);
+        transmitter.from_frame(&frame).unwrap();
+        assert!(manager.tick(Duration::from_millis(0)).is_none());
+
+        let mut message = None;
+        while let Some(frame) = transmitter.next_frame() {
+            if let Some((id, pgn, payload)) = manager.from_frame(&frame).unwrap() {
+                assert_eq!(id.source_address(), 0x10);
+                message = Some((pgn, payload.len()));
+            }
+        }
+        assert_eq!(message, Some((PGN::SoftwareIdentification, 20)));
+
+        let frame = manager.tick(Duration::from_millis(10)).unwrap();
+        transmitter.from_frame(&frame).unwrap();
+        assert!(transmitter.is_complete());
+        assert_eq!(manager.session_count(), 0);
+    }
+
+    #[test]
+    fn test_reassembly_manager_reject() {
+        let mut manager = ReassemblyManager::<1>::new(0x80);
+
+        let mut transport = BroadcastTransport::new(0x10, PGN::SoftwareIdentification)
+            .with_data(&[0x11; 10])
+            .unwrap();
+        manager
+            .from_frame(&transport.next_frame().unwrap())
+            .unwrap();
+
+        let mut transmitter = ConnectionTransmitter::new(0x20, 0x80, PGN::ComponentIdentification)
+            .with_data(&[0x22; 10])
+            .unwrap();
+        assert_eq!(
+            manager.from_frame(&transmitter.next_frame().unwrap()),
+            Err(TransportError::AlreadyInSession)
+        );
+
+        let frame = manager.tick(Duration::from_millis(0)).unwrap();
+        assert_eq!(frame.id().as_raw(), 0x1CEC2080);
+        assert_eq!(
+            transmitter.from_frame(&frame),
+            Err(TransportError::Aborted(AbortReason::AlreadyInSession))
+        );
+    }
+
+    #[test]
+    fn test_reassembly_manager_bad_sequence() {
+        let mut manager = ReassemblyManager::<1>::new(0x80);
+
+        let mut transmitter = ConnectionTransmitter::new(0x20, 0x80, PGN::ComponentIdentification)
+            .with_data(&[0x22; 20])
+            .unwrap();
+        manager
+            .from_frame(&transmitter.next_frame().unwrap())
+            .unwrap();
+        transmitter
+            .from_frame(&manager.tick(Duration::from_millis(0)).unwrap())
+            .unwrap();
+
+        transmitter.next_frame();
+        assert_eq!(
+            manager.from_frame(&transmitter.next_frame().unwrap()),
+            Err(TransportError::BadSequence)
+        );
+
+        let frame = manager.tick(Duration::from_millis(0)).unwrap();
+        assert_eq!(
+            ConnectionAbort::from_pdu(frame.as_ref()).unwrap().reason,
+            AbortReason::BadSequence
+        );
+        assert_eq!(manager.session_count(), 0);
+    }
+
+    #[test]
+    fn test_transmitter_data_too_long() {
+        let data = [0; DATA_MAX_LENGTH + 1];
+
+        assert_eq!(
+            BroadcastTransmitter::new(0x01, PGN::SoftwareIdentification)
+                .with_data(&data)
+                .err(),
+            Some(TransportError::BadLength)
+        );
+        assert_eq!(
+            ConnectionTransmitter::new(0x01, 0x02, PGN::SoftwareIdentification)
+                .with_data(&data)
+                .err(),
+            Some(TransportError::BadLength)
+        );
+        assert!(
+            ConnectionTransmitter::new(0x01, 0x02, PGN::SoftwareIdentification)
+                .with_data(&data[..DATA_MAX_LENGTH])
+                .is_ok()
+        );
+        assert_eq!(
+            BroadcastTransport::new(0x01, PGN::SoftwareIdentification)
+                .with_data(&data)
+                .err(),
+            Some(TransportError::BadLength)
+        );
+    }
+
+    #[test]
+    fn test_reassembly_manager_empty_frame() {
+        let mut manager = ReassemblyManager::<1>::new(0x80);
+
+        let frame = FrameBuilder::new(
+            IdBuilder::from_pgn(PGN::TransportProtocolConnectionManagement)
+                .sa(0x20)
+                .da(0x80)
+                .build(),
+        )
+        .set_len(0)
+        .build();
+
+        assert_eq!(manager.from_frame(&frame), Ok(None));
+        assert_eq!(manager.session_count(), 0);
+    }
+
+    #[test]
+    fn test_extended_transport() {
+        let mut data = [0; 2000];
+        for (i, byte) in data.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+
+        let mut transmitter = ExtendedTransmitter::new(0x01, 0x02, PGN::SoftwareIdentification)
+            .with_data(&data)
+            .unwrap();
+        let mut receiver = ExtendedReceiver::new(0x02).with_window(100);
+
+        let frame = transmitter.next_frame().unwrap();
+        assert_eq!(frame.id().as_raw(), 0x1CC80201);
+        assert_eq!(
+            frame.as_ref(),
+            &[0x14, 0xD0, 0x07, 0x00, 0x00, 0xDA, 0xFE, 0x00]
+        );
+
+        receiver.from_frame(&frame).unwrap();
+        assert_eq!(receiver.len(), 2000);
+        assert_eq!(receiver.packet_count(), 286);
+
+        let frame = receiver.next_frame().unwrap();
+        assert_eq!(frame.id().as_raw(), 0x1CC80102);
+        assert_eq!(
+            frame.as_ref(),
+            &[0x15, 0x64, 0x01, 0x00, 0x00, 0xDA, 0xFE, 0x00]
+        );
+        transmitter.from_frame(&frame).unwrap();
+
+        let mut buffer = [0; 2000];
+        let mut data_packet_offset = 0;
+        while !transmitter.is_complete() {
+            while let Some(frame) = transmitter.next_frame() {
+                if frame.id().pgn() == PGN::ExtendedTransportProtocolConnectionManagement {
+                    data_packet_offset += 1;
+                }
+                if let Some((offset, chunk)) = receiver.from_frame(&frame).unwrap() {
+                    buffer[offset..offset + chunk.len()].copy_from_slice(chunk);
+                }
+            }
+            while let Some(frame) = receiver.next_frame() {
+                transmitter.from_frame(&frame).unwrap();
+            }
+        }
+
+        assert_eq!(data_packet_offset, 3);
+        assert!(receiver.is_complete());
+        assert_eq!(buffer, data);
+    }
+
+    #[test]
+    fn test_extended_transmitter_data_length() {
+        let data = [0; DATA_MAX_LENGTH + 1];
+        let mut source = |_: usize, _: &mut [u8]| true;
+
+        assert_eq!(
+            ExtendedTransmitter::new(0x01, 0x02, PGN::SoftwareIdentification)
+                .with_data(&data[..DATA_MAX_LENGTH])
+                .err(),
+            Some(TransportError::BadLength)
+        );
+        assert!(
+            ExtendedTransmitter::new(0x01, 0x02, PGN::SoftwareIdentification)
+                .with_data(&data)
+                .is_ok()
+        );
+        assert_eq!(
+            ExtendedTransmitter::new(0x01, 0x02, PGN::SoftwareIdentification)
+                .with_source(DATA_MAX_LENGTH, &mut source)
+                .err(),
+            Some(TransportError::BadLength)
+        );
+        assert_eq!(
+            ExtendedTransmitter::new(0x01, 0x02, PGN::SoftwareIdentification)
+                .with_source(EXTENDED_DATA_MAX_LENGTH + 1, &mut source)
+                .err(),
+            Some(TransportError::BadLength)
+        );
+    }
+
+    #[test]
+    fn test_extended_transport_source() {
+        let mut source = |offset: usize, buffer: &mut [u8]| {
+            for (i, byte) in buffer.iter_mut().enumerate() {
+                *byte = (offset + i) as u8;
+            }
+            true
+        };
+
+        let mut transmitter = ExtendedTransmitter::new(0x01, 0x02, PGN::SoftwareIdentification)
+            .with_source(100_000, &mut source)
+            .unwrap();
+        let mut receiver = ExtendedReceiver::new(0x02);
+
+        let mut length = 0;
+        while !transmitter.is_complete() {
+            while let Some(frame) = transmitter.next_frame() {
+                if let Some((offset, chunk)) = receiver.from_frame(&frame).unwrap() {
+                    for (i, byte) in chunk.iter().enumerate() {
+                        assert_eq!(*byte, (offset + i) as u8);
+                    }
+                    length += chunk.len();
+                }
+            }
+            while let Some(frame) = receiver.next_frame() {
+                transmitter.from_frame(&frame).unwrap();
+            }
+        }
+
+        assert!(receiver.is_complete());
+        assert_eq!(length, 100_000);
+    }
+
+    #[test]
+    fn test_extended_transport_source_unavailable() {
+        let mut source = |offset: usize, _: &mut [u8]| offset < 14;
+
+        let mut transmitter = ExtendedTransmitter::new(0x01, 0x02, PGN::SoftwareIdentification)
+            .with_source(2000, &mut source)
+            .unwrap();
+        let mut receiver = ExtendedReceiver::new(0x02);
+
+        receiver
+            .from_frame(&transmitter.next_frame().unwrap())
+            .unwrap();
+        transmitter
+            .from_frame(&receiver.next_frame().unwrap())
+            .unwrap();
+
+        assert!(transmitter.next_frame().is_some());
+        assert!(transmitter.next_frame().is_some());
+        assert!(transmitter.next_frame().is_some());
+
+        let frame = transmitter.next_frame().unwrap();
+        assert_eq!(
+            frame.id().pgn(),
+            PGN::ExtendedTransportProtocolConnectionManagement
+        );
+        assert_eq!(frame.as_ref()[..2], [0xFF, 0xFA]);
+        assert_eq!(
+            transmitter.state(),
+            ExtendedTransmitterState::Aborted(AbortReason::Other)
+        );
+    }
+
+    #[test]
+    fn test_extended_transport_data_packet_offset() {
+        let data = [0x55; 1800];
+
+        let mut transmitter = ExtendedTransmitter::new(0x01, 0x02, PGN::SoftwareIdentification)
+            .with_data(&data)
+            .unwrap();
+        let mut receiver = ExtendedReceiver::new(0x02);
+
+        receiver
+            .from_frame(&transmitter.next_frame().unwrap())
+            .unwrap();
+
+        let frame = extended_packet_frame(
+            0x01,
+            0x02,
+            ExtendedConnectionManagement::DataPacketOffset,
+            1,
+            0,
+            PGN::SoftwareIdentification,
+        );
+        assert_eq!(
+            receiver.from_frame(&frame),
+            Err(TransportError::UnexpectedDataPacketOffset)
+        );
+
+        transmitter
+            .from_frame(&receiver.next_frame().unwrap())
+            .unwrap();
+
+        let frame = extended_packet_frame(
+            0x01,
+            0x02,
+            ExtendedConnectionManagement::DataPacketOffset,
+            255,
+            10,
+            PGN::SoftwareIdentification,
+        );
+        assert_eq!(
+            receiver.from_frame(&frame),
+            Err(TransportError::BadDataPacketOffset)
+        );
+
+        let frame = transmitter.next_frame().unwrap();
+        assert_eq!(
+            frame.as_ref(),
+            &[0x16, 0xFF, 0x00, 0x00, 0x00, 0xDA, 0xFE, 0x00]
+        );
+        receiver.from_frame(&frame).unwrap();
+
+        let frame = transmitter.next_frame().unwrap();
+        assert_eq!(frame.id().as_raw(), 0x1CC70201);
+        assert_eq!(
+            receiver.from_frame(&frame).unwrap(),
+            Some((0, &[0x55; 7][..]))
+        );
+    }
+
+    #[test]
+    fn test_extended_receiver_bad_length() {
+        let mut receiver = ExtendedReceiver::new(0x02);
+
+        let frame = extended_size_frame(
+            0x01,
+            0x02,
+            ExtendedConnectionManagement::RequestToSend,
+            DATA_MAX_LENGTH as u32,
+            PGN::SoftwareIdentification,
+        );
+        assert_eq!(receiver.from_frame(&frame), Err(TransportError::BadLength));
+
+        let frame = extended_size_frame(
+            0x01,
+            0x02,
+            ExtendedConnectionManagement::RequestToSend,
+            EXTENDED_DATA_MAX_LENGTH as u32 + 1,
+            PGN::SoftwareIdentification,
+        );
+        assert_eq!(receiver.from_frame(&frame), Err(TransportError::BadLength));
+        assert!(!receiver.is_active());
+    }
+
+    #[test]
+    fn test_extended_receiver_timeout() {
+        let data = [0; 1800];
+
+        let mut transmitter = ExtendedTransmitter::new(0x01, 0x02, PGN::SoftwareIdentification)
+            .with_data(&data)
+            .unwrap();
+        let mut receiver = ExtendedReceiver::new(0x02);
+
+        receiver
+            .from_frame(&transmitter.next_frame().unwrap())
+            .unwrap();
+        assert!(receiver.tick(Duration::from_millis(0)).is_some());
+        assert!(receiver.tick(Duration::from_millis(1000)).is_none());
+
+        let frame = receiver.tick(Duration::from_millis(2300)).unwrap();
+        assert_eq!(frame.id().as_raw(), 0x1CC80102);
+        assert_eq!(
+            frame.as_ref(),
+            &[0xFF, 0x03, 0xFF, 0xFF, 0xFF, 0xDA, 0xFE, 0x00]
+        );
+        assert_eq!(
+            receiver.state(),
+            ExtendedReceiverState::Aborted(AbortReason::Timeout)
+        );
+    }
+
+    #[test]
+    fn test_fast_packet() {
+        let data = [
+            0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E,
+            0x0F, 0x10,
+        ];
+        let id = IdBuilder::from_pgn(PGN::Other(129029))
+            .priority(3)
+            .sa(0x23)
+            .build();
+
+        let mut transmitter = FastPacketTransmitter::new(id, 5).with_data(&data).unwrap();
+        assert_eq!(transmitter.frame_count(), 3);
+
+        let frame = transmitter.next_frame().unwrap();
+        assert_eq!(frame.id(), &id);
+        assert_eq!(
+            frame.as_ref(),
+            &[0xA0, 0x10, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06]
+        );
+
+        let mut receiver = FastPacketReceiver::new();
+        receiver.from_frame(&frame).unwrap();
+        assert!(receiver.is_active());
+
+        let frame = transmitter.next_frame().unwrap();
+        assert_eq!(
+            frame.as_ref(),
+            &[0xA1, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D]
+        );
+        receiver.from_frame(&frame).unwrap();
+
+        let frame = transmitter.next_frame().unwrap();
+        assert_eq!(
+            frame.as_ref(),
+            &[0xA2, 0x0E, 0x0F, 0x10, 0xFF, 0xFF, 0xFF, 0xFF]
+        );
+        receiver.from_frame(&frame).unwrap();
+
+        assert!(transmitter.is_complete());
+        assert!(transmitter.next_frame().is_none());
+        assert!(receiver.is_complete());
+        assert_eq!(receiver.id(), id);
+        assert_eq!(receiver.sequence(), 5);
+        assert_eq!(receiver.data(), &data);
+    }
+
+    #[test]
+    fn test_fast_packet_single_frame() {
+        let id = IdBuilder::from_pgn(PGN::Other(126720)).sa(0x23).build();
+
+        let mut transmitter = FastPacketTransmitter::new(id, 9)
+            .with_data(&[0x01, 0x02])
+            .unwrap();
+        assert_eq!(transmitter.sequence(), 1);
+
+        let frame = transmitter.next_frame().unwrap();
+        assert_eq!(
+            frame.as_ref(),
+            &[0x20, 0x02, 0x01, 0x02, 0xFF, 0xFF, 0xFF, 0xFF]
+        );
+        assert!(transmitter.next_frame().is_none());
+
+        let mut receiver = FastPacketReceiver::new();
+        receiver.from_frame(&frame).unwrap();
+        assert!(receiver.is_complete());
+        assert_eq!(receiver.data(), &[0x01, 0x02]);
+    }
+
+    #[test]
+    fn test_fast_packet_maximum_length() {
+        let data = [0x55; 300];
+        let id = IdBuilder::from_pgn(PGN::Other(126996)).sa(0x23).build();
+
+        assert!(FastPacketTransmitter::new(id, 0).with_data(&data).is_err());
+
+        let data = &data[..FAST_PACKET_DATA_MAX_LENGTH];
+        let mut transmitter = FastPacketTransmitter::new(id, 0).with_data(data).unwrap();
+        assert_eq!(transmitter.len(), FAST_PACKET_DATA_MAX_LENGTH);
+        assert_eq!(transmitter.frame_count(), 32);
+
+        let mut receiver = FastPacketReceiver::new();
+        while let Some(frame) = transmitter.next_frame() {
+            receiver.from_frame(&frame).unwrap();
+        }
+
+        assert!(receiver.is_complete());
+        assert_eq!(receiver.data(), data);
+    }
+
+    #[test]
+    fn test_fast_packet_bad_sequence() {
+        let id = IdBuilder::from_pgn(PGN::Other(129029)).sa(0x23).build();
+
+        let mut receiver = FastPacketReceiver::new();
+        assert_eq!(
+            receiver.from_frame(&Frame::new(id, [0x21, 0, 0, 0, 0, 0, 0, 0])),
+            Err(TransportError::UnexpectedDataTransfer)
+        );
+
+        receiver
+            .from_frame(&Frame::new(id, [0x20, 20, 0, 0, 0, 0, 0, 0]))
+            .unwrap();
+        assert_eq!(
+            receiver.from_frame(&Frame::new(id, [0x22, 0, 0, 0, 0, 0, 0, 0])),
+            Err(TransportError::BadSequence)
+        );
+        assert_eq!(
+            receiver.from_frame(&Frame::new(id, [0x41, 0, 0, 0, 0, 0, 0, 0])),
+            Err(TransportError::UnexpectedDataTransfer)
+        );
+
+        receiver
+            .from_frame(&Frame::new(id, [0x21, 0, 0, 0, 0, 0, 0, 0]))
+            .unwrap();
+        assert_eq!(
+            receiver.from_frame(&Frame::new(id, [0x21, 0, 0, 0, 0, 0, 0, 0])),
+            Err(TransportError::DuplicateSequence)
+        );
+        assert_eq!(
+            receiver.from_frame(&Frame::new(id, [0x20, 224, 0, 0, 0, 0, 0, 0])),
+            Err(TransportError::BadLength)
+        );
+        assert!(receiver.is_active());
+    }
+
+    #[test]
+    fn test_fast_packet_manager() {
+        let data0 = [0x11; 20];
+        let data1 = [0x22; 10];
+        let id0 = IdBuilder::from_pgn(PGN::Other(129029)).sa(0x10).build();
+        let id1 = IdBuilder::from_pgn(PGN::Other(129029)).sa(0x20).build();
+
+        let mut transmitter0 = FastPacketTransmitter::new(id0, 1)
+            .with_data(&data0)
+            .unwrap();
+        let mut transmitter1 = FastPacketTransmitter::new(id1, 2)
+            .with_data(&data1)
+            .unwrap();
+
+        let mut manager = FastPacketManager::<2>::new();
+
+        let frame = transmitter0.next_frame().unwrap();
+        assert!(manager.from_frame(&frame).unwrap().is_none());
+        let frame = transmitter1.next_frame().unwrap();
+        assert!(manager.from_frame(&frame).unwrap().is_none());
+        assert_eq!(manager.session_count(), 2);
+
+        let id2 = IdBuilder::from_pgn(PGN::Other(129029)).sa(0x30).build();
+        let frame = FastPacketTransmitter::new(id2, 0)
+            .with_data(&[0; 8])
+            .unwrap()
+            .next_frame()
+            .unwrap();
+        assert_eq!(
+            manager.from_frame(&frame),
+            Err(TransportError::InsufficientResources)
+        );
+
+        let frame = transmitter0.next_frame().unwrap();
+        assert!(manager.from_frame(&frame).unwrap().is_none());
+
+        let frame = transmitter1.next_frame().unwrap();
+        let (id, pgn, data) = manager.from_frame(&frame).unwrap().unwrap();
+        assert_eq!(id, id1);
+        assert_eq!(pgn, PGN::Other(129029));
+        assert_eq!(data, &data1);
+
+        let frame = transmitter0.next_frame().unwrap();
+        let (id, _, data) = manager.from_frame(&frame).unwrap().unwrap();
+        assert_eq!(id, id0);
+        assert_eq!(data, &data0);
+        assert_eq!(manager.session_count(), 0);
     }
 }